@@ -0,0 +1,146 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rust_roguelike_core::algorithm::generation::cave::CaveGenerator;
+use rust_roguelike_core::algorithm::pathfinding::a_star::AStar;
+use rust_roguelike_core::algorithm::pathfinding::context::SearchContext;
+use rust_roguelike_core::algorithm::pathfinding::hpa::HierarchicalAStar;
+use rust_roguelike_core::algorithm::pathfinding::jps::{GridGraph, GridMovement, JumpPointSearch};
+use rust_roguelike_core::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+use rust_roguelike_core::math::random::Random;
+use rust_roguelike_core::math::size2d::Size2d;
+
+const SIZE: u32 = 100;
+const LARGE_SIZE: u32 = 500;
+const CLUSTER_SIZE: u32 = 16;
+const SEEDS: u64 = 50;
+
+/// The random maps of the JPS tests with 25% obstacles and a random start & goal
+fn create_random_maps() -> Vec<(OccupancyMap, usize, usize)> {
+    let size = Size2d::new(20, 15);
+
+    (0..SEEDS)
+        .map(|seed| {
+            let mut random = Random::new(seed);
+            let mut map = OccupancyMap::new(size, false);
+            map.add_random_nodes(&mut random, 25);
+            map.add_border();
+
+            let free: Vec<usize> = (0..size.get_tiles())
+                .filter(|index| !map.is_occupied[*index])
+                .collect();
+            let start = *random.choose(&free).unwrap();
+            let goal = *random.choose(&free).unwrap();
+
+            (map, start, goal)
+        })
+        .collect()
+}
+
+fn get_cost(result: &PathfindingResult) -> Option<u32> {
+    match result {
+        PathfindingResult::Path { cost, .. } => Some(*cost),
+        _ => None,
+    }
+}
+
+/// The empty map with a border of the A* tests, but larger
+fn create_open_map() -> Vec<(OccupancyMap, usize, usize)> {
+    let size = Size2d::new(SIZE, SIZE);
+    let mut map = OccupancyMap::new(size, false);
+    map.add_border();
+    let start = size.to_index(1, 1);
+    let goal = size.to_index(SIZE - 2, SIZE - 2);
+
+    vec![(map, start, goal)]
+}
+
+fn compare_pathfinding(c: &mut Criterion) {
+    for (name, maps) in [
+        ("random", create_random_maps()),
+        ("open", create_open_map()),
+    ] {
+        let mut group = c.benchmark_group(name);
+        let mut context = SearchContext::default();
+        let a_star = AStar::default();
+
+        for (map, start, goal) in &maps {
+            let grid = GridGraph::new(map, GridMovement::Diagonal);
+            let jps = JumpPointSearch::for_map(map, GridMovement::Diagonal);
+
+            assert_eq!(
+                get_cost(&a_star.find(&grid, *start, *goal)),
+                get_cost(&jps.find(&grid, *start, *goal))
+            );
+        }
+
+        group.bench_function("a_star_4", |b| {
+            b.iter(|| {
+                for (map, start, goal) in &maps {
+                    a_star.find_with_context(&mut context, map, *start, *goal);
+                }
+            })
+        });
+
+        group.bench_function("jps_4", |b| {
+            b.iter(|| {
+                for (map, start, goal) in &maps {
+                    let jps = JumpPointSearch::for_map(map, GridMovement::Orthogonal);
+                    jps.find_with_context(&mut context, map, *start, *goal);
+                }
+            })
+        });
+
+        group.bench_function("a_star_8", |b| {
+            b.iter(|| {
+                for (map, start, goal) in &maps {
+                    let grid = GridGraph::new(map, GridMovement::Diagonal);
+                    a_star.find_with_context(&mut context, &grid, *start, *goal);
+                }
+            })
+        });
+
+        group.bench_function("jps_8", |b| {
+            b.iter(|| {
+                for (map, start, goal) in &maps {
+                    let grid = GridGraph::new(map, GridMovement::Diagonal);
+                    let jps = JumpPointSearch::for_map(map, GridMovement::Diagonal);
+                    jps.find_with_context(&mut context, &grid, *start, *goal);
+                }
+            })
+        });
+
+        group.finish();
+    }
+}
+
+/// Returns the first & the last free tile, which are in opposite corners of the map
+fn find_start_and_goal(map: &OccupancyMap) -> (usize, usize) {
+    let start = map.is_occupied.iter().position(|o| !o).unwrap();
+    let goal = map.is_occupied.iter().rposition(|o| !o).unwrap();
+    (start, goal)
+}
+
+fn compare_large_map(c: &mut Criterion) {
+    let map = CaveGenerator::default().generate(Size2d::new(LARGE_SIZE, LARGE_SIZE), 42);
+    let (start, goal) = find_start_and_goal(&map);
+    let mut group = c.benchmark_group("large_cave");
+    group.sample_size(10);
+    let mut context = SearchContext::default();
+
+    let a_star = AStar::default();
+    group.bench_function("a_star", |b| {
+        b.iter(|| a_star.find_with_context(&mut context, &map, start, goal))
+    });
+
+    let hierarchy = HierarchicalAStar::new(&map, CLUSTER_SIZE);
+    group.bench_function("hpa", |b| b.iter(|| hierarchy.find(&map, start, goal)));
+
+    group.bench_function("hpa_build", |b| {
+        b.iter(|| HierarchicalAStar::new(&map, CLUSTER_SIZE))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, compare_pathfinding, compare_large_map);
+criterion_main!(benches);
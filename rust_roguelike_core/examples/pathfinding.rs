@@ -55,7 +55,7 @@ impl App for PathfindingExample {
 
         tile_renderer.render_ascii(renderer, self.start, b'S', GREEN);
 
-        if let PathfindingResult::Path { indices } = &self.result {
+        if let PathfindingResult::Path { indices, .. } = &self.result {
             for node in indices {
                 tile_renderer.render_ascii(renderer, *node, b'+', BLUE);
             }
@@ -0,0 +1,295 @@
+use crate::algorithm::pathfinding::CostCalculator;
+use crate::math::graph::Graph;
+
+/// Returns all valid nodes reachable from the start node, including the start node itself
+///
+/// ```
+///# use rust_roguelike_core::algorithm::connectivity::flood_fill;
+///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+///# use rust_roguelike_core::math::size2d::Size2d;
+/// let mut map = OccupancyMap::new(Size2d::new(5, 3), false);
+/// map.add_border();
+/// map.set_node(7, true);
+///
+/// assert_eq!(flood_fill(&map, 6), vec![6]);
+/// assert_eq!(flood_fill(&map, 0), Vec::<usize>::new());
+/// ```
+pub fn flood_fill<N, E, G>(graph: &G, start: usize) -> Vec<usize>
+where
+    G: Graph<N, E> + CostCalculator<E>,
+{
+    let mut is_visited = vec![false; graph.get_size()];
+    flood_fill_unvisited(graph, start, &mut is_visited)
+}
+
+/// The connected regions of a graph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Regions {
+    labels: Vec<Option<usize>>,
+    sizes: Vec<usize>,
+}
+
+impl Regions {
+    /// Labels all connected regions of valid nodes
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::connectivity::Regions;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut map = OccupancyMap::new(Size2d::new(6, 3), false);
+    /// map.add_border();
+    /// map.set_node(8, true);
+    /// let regions = Regions::new(&map);
+    ///
+    /// assert_eq!(regions.get_count(), 2);
+    /// assert_eq!(regions.get_region(7), Some(0));
+    /// assert_eq!(regions.get_region(9), Some(1));
+    /// assert_eq!(regions.get_region(0), None);
+    /// assert_eq!(regions.get_largest(), Some(1));
+    /// assert_eq!(regions.get_indices(1), vec![9, 10]);
+    /// ```
+    pub fn new<N, E, G>(graph: &G) -> Regions
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let mut labels = vec![None; graph.get_size()];
+        let mut sizes = Vec::new();
+        let mut is_visited = vec![false; graph.get_size()];
+
+        for index in 0..graph.get_size() {
+            if is_visited[index] || !graph.is_valid(index) {
+                continue;
+            }
+
+            let region = flood_fill_unvisited(graph, index, &mut is_visited);
+
+            for node in &region {
+                labels[*node] = Some(sizes.len());
+            }
+
+            sizes.push(region.len());
+        }
+
+        Regions { labels, sizes }
+    }
+
+    /// Returns the number of regions
+    pub fn get_count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Returns the region of a node or `None` for invalid nodes
+    pub fn get_region(&self, index: usize) -> Option<usize> {
+        self.labels.get(index).cloned().flatten()
+    }
+
+    /// Returns the number of nodes in a region
+    pub fn get_size(&self, region: usize) -> usize {
+        self.sizes.get(region).cloned().unwrap_or(0)
+    }
+
+    /// Returns the region with the most nodes. The first one wins ties.
+    pub fn get_largest(&self) -> Option<usize> {
+        self.sizes
+            .iter()
+            .enumerate()
+            .max_by(|(a, size_a), (b, size_b)| size_a.cmp(size_b).then(b.cmp(a)))
+            .map(|(region, _)| region)
+    }
+
+    /// Returns the sorted indices of all nodes in a region
+    pub fn get_indices(&self, region: usize) -> Vec<usize> {
+        self.labels
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| **label == Some(region))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// Returns the sorted indices of the largest connected region
+pub fn find_largest_region<N, E, G>(graph: &G) -> Vec<usize>
+where
+    G: Graph<N, E> + CostCalculator<E>,
+{
+    let regions = Regions::new(graph);
+
+    regions
+        .get_largest()
+        .map(|region| regions.get_indices(region))
+        .unwrap_or_default()
+}
+
+/// Returns the sorted indices of all chokepoints.
+///
+/// A chokepoint is a node, whose removal splits its region into several parts.
+/// See [articulation points](https://en.wikipedia.org/wiki/Biconnected_component).
+///
+/// ```
+///# use rust_roguelike_core::algorithm::connectivity::find_chokepoints;
+///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+///# use rust_roguelike_core::math::size2d::Size2d;
+/// // #######
+/// // #..#..#
+/// // #.....#
+/// // #..#..#
+/// // #######
+/// let mut map = OccupancyMap::new(Size2d::new(7, 5), false);
+/// map.add_border();
+/// map.set_node(10, true);
+/// map.set_node(24, true);
+///
+/// assert_eq!(find_chokepoints(&map), vec![16, 17, 18]);
+/// ```
+pub fn find_chokepoints<N, E, G>(graph: &G) -> Vec<usize>
+where
+    G: Graph<N, E> + CostCalculator<E>,
+{
+    let size = graph.get_size();
+    let mut discovery = vec![usize::MAX; size];
+    let mut low = vec![0; size];
+    let mut is_chokepoint = vec![false; size];
+    let mut time = 0;
+
+    for root in 0..size {
+        if discovery[root] != usize::MAX || !graph.is_valid(root) {
+            continue;
+        }
+
+        discovery[root] = time;
+        low[root] = time;
+        time += 1;
+
+        let mut root_children = 0;
+        let mut stack = vec![(root, None, get_valid_neighbors(graph, root), 0)];
+
+        while let Some((node, parent, neighbors, next)) = stack.last_mut() {
+            let node = *node;
+
+            if let Some(&neighbor) = neighbors.get(*next) {
+                *next += 1;
+
+                if discovery[neighbor] == usize::MAX {
+                    discovery[neighbor] = time;
+                    low[neighbor] = time;
+                    time += 1;
+
+                    if node == root {
+                        root_children += 1;
+                    }
+
+                    let neighbors = get_valid_neighbors(graph, neighbor);
+                    stack.push((neighbor, Some(node), neighbors, 0));
+                } else if Some(neighbor) != *parent {
+                    low[node] = low[node].min(discovery[neighbor]);
+                }
+            } else {
+                let parent = *parent;
+                stack.pop();
+
+                if let Some(parent) = parent {
+                    low[parent] = low[parent].min(low[node]);
+
+                    if parent != root && low[node] >= discovery[parent] {
+                        is_chokepoint[parent] = true;
+                    }
+                }
+            }
+        }
+
+        if root_children > 1 {
+            is_chokepoint[root] = true;
+        }
+    }
+
+    (0..size).filter(|index| is_chokepoint[*index]).collect()
+}
+
+fn get_valid_neighbors<N, E, G>(graph: &G, index: usize) -> Vec<usize>
+where
+    G: Graph<N, E> + CostCalculator<E>,
+{
+    graph
+        .get_neighbors(index)
+        .into_iter()
+        .map(|neighbor| neighbor.index)
+        .filter(|neighbor| graph.is_valid(*neighbor))
+        .collect()
+}
+
+fn flood_fill_unvisited<N, E, G>(graph: &G, start: usize, is_visited: &mut [bool]) -> Vec<usize>
+where
+    G: Graph<N, E> + CostCalculator<E>,
+{
+    if !graph.is_valid(start) {
+        return Vec::new();
+    }
+
+    let mut region = Vec::new();
+    let mut open_nodes = vec![start];
+    is_visited[start] = true;
+
+    while let Some(index) = open_nodes.pop() {
+        region.push(index);
+
+        for neighbor in get_valid_neighbors(graph, index) {
+            if !is_visited[neighbor] {
+                is_visited[neighbor] = true;
+                open_nodes.push(neighbor);
+            }
+        }
+    }
+
+    region
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::graph::occupancy::OccupancyMap;
+    use crate::math::size2d::Size2d;
+
+    #[test]
+    fn test_flood_fill_open_room() {
+        let mut map = OccupancyMap::new(Size2d::new(5, 5), false);
+        map.add_border();
+
+        let mut region = flood_fill(&map, 12);
+        region.sort_unstable();
+
+        assert_eq!(region, vec![6, 7, 8, 11, 12, 13, 16, 17, 18]);
+    }
+
+    #[test]
+    fn test_no_chokepoints_in_open_room() {
+        let mut map = OccupancyMap::new(Size2d::new(6, 6), false);
+        map.add_border();
+
+        assert!(find_chokepoints(&map).is_empty());
+    }
+
+    #[test]
+    fn test_corridor_is_chokepoint() {
+        // #####
+        // #...#
+        // ###.#
+        // #...#
+        // #####
+        let mut map = OccupancyMap::new(Size2d::new(5, 5), false);
+        map.add_border();
+        map.set_node(11, true);
+        map.set_node(12, true);
+
+        assert_eq!(find_chokepoints(&map), vec![7, 8, 13, 17, 18]);
+    }
+
+    #[test]
+    fn test_largest_region() {
+        let mut map = OccupancyMap::new(Size2d::new(7, 3), false);
+        map.add_border();
+        map.set_node(9, true);
+
+        assert_eq!(find_largest_region(&map), vec![10, 11, 12]);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::math::graph::occupancy::OccupancyMap;
+use crate::math::random::Random;
+use crate::math::rectangle::Rectangle;
+use crate::math::size2d::Size2d;
+
+/// The tree created by the [`BspGenerator`].
+///
+/// Each branch covers an area of the map, which allows to assign themes per branch.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BspTree {
+    /// An area with a single room
+    Leaf { area: Rectangle, room: Rectangle },
+    /// An area split into 2 smaller areas
+    Split {
+        area: Rectangle,
+        first: Box<BspTree>,
+        second: Box<BspTree>,
+    },
+}
+
+impl BspTree {
+    /// Returns the area covered by this branch
+    pub fn get_area(&self) -> Rectangle {
+        match self {
+            BspTree::Leaf { area, .. } => *area,
+            BspTree::Split { area, .. } => *area,
+        }
+    }
+
+    /// Returns all rooms of this branch
+    pub fn get_rooms(&self) -> Vec<Rectangle> {
+        let mut rooms = Vec::new();
+        self.collect_rooms(&mut rooms);
+        rooms
+    }
+
+    /// Returns the number of levels of this branch
+    pub fn get_depth(&self) -> u32 {
+        match self {
+            BspTree::Leaf { .. } => 1,
+            BspTree::Split { first, second, .. } => 1 + first.get_depth().max(second.get_depth()),
+        }
+    }
+
+    fn collect_rooms(&self, rooms: &mut Vec<Rectangle>) {
+        match self {
+            BspTree::Leaf { room, .. } => rooms.push(*room),
+            BspTree::Split { first, second, .. } => {
+                first.collect_rooms(rooms);
+                second.collect_rooms(rooms);
+            }
+        }
+    }
+}
+
+/// Generates rooms with binary space partitioning.
+///
+/// The map is recursively split until all areas are small enough.
+/// Then a room is placed in each leaf and the rooms of siblings are connected by corridors.
+///
+/// See [RogueBasin](http://roguebasin.com/index.php/Basic_BSP_Dungeon_generation)
+#[derive(Clone, Copy, Debug)]
+pub struct BspGenerator {
+    min_leaf_size: u32,
+    max_leaf_size: u32,
+    split_ratio: u32,
+    min_room_size: u32,
+}
+
+impl Default for BspGenerator {
+    fn default() -> Self {
+        BspGenerator::new(6, 16, 30, 3)
+    }
+}
+
+impl BspGenerator {
+    /// Creates a new BspGenerator
+    ///
+    /// * `min_leaf_size` - the minimum width & height of a leaf, which is at least 1
+    /// * `max_leaf_size` - areas with a larger width or height are split
+    /// * `split_ratio` - the minimum percentage of the smaller part of a split between 1 and 50
+    /// * `min_room_size` - the minimum width & height of a room, which is at least 1 and should be at least 2 smaller than `min_leaf_size`
+    pub fn new(
+        min_leaf_size: u32,
+        max_leaf_size: u32,
+        split_ratio: u32,
+        min_room_size: u32,
+    ) -> BspGenerator {
+        BspGenerator {
+            min_leaf_size: min_leaf_size.max(1),
+            max_leaf_size,
+            split_ratio: split_ratio.clamp(1, 50),
+            min_room_size: min_room_size.max(1),
+        }
+    }
+
+    /// Generates the tree and a map with connected rooms
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::generation::bsp::BspGenerator;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let generator = BspGenerator::default();
+    /// let size = Size2d::new(40, 30);
+    /// let (tree, map) = generator.generate(size, 42);
+    ///
+    /// for room in tree.get_rooms() {
+    ///     let [x, y] = room.center();
+    ///     assert!(!map.is_occupied[size.to_index(x, y)]);
+    /// }
+    /// ```
+    pub fn generate(&self, size: Size2d, seed: u64) -> (BspTree, OccupancyMap) {
+        let mut random = Random::new(seed);
+        let mut map = OccupancyMap::new(size, true);
+        let tree = self.split(&mut random, &mut map, Rectangle::new(0, 0, size));
+
+        (tree, map)
+    }
+
+    fn split(&self, random: &mut Random, map: &mut OccupancyMap, area: Rectangle) -> BspTree {
+        let width = area.size().width();
+        let height = area.size().height();
+        let can_split_x = width > self.max_leaf_size && width >= 2 * self.min_leaf_size;
+        let can_split_y = height > self.max_leaf_size && height >= 2 * self.min_leaf_size;
+
+        let split_x = match (can_split_x, can_split_y) {
+            (false, false) => return self.create_leaf(random, map, area),
+            (true, false) => true,
+            (false, true) => false,
+            (true, true) => width > height || (width == height && random.check_percentage(50)),
+        };
+
+        let (first_area, second_area) = if split_x {
+            let position = self.calculate_split(random, width);
+            (
+                Rectangle::new(area.x(), area.y(), Size2d::new(position, height)),
+                Rectangle::new(
+                    area.x() + position,
+                    area.y(),
+                    Size2d::new(width - position, height),
+                ),
+            )
+        } else {
+            let position = self.calculate_split(random, height);
+            (
+                Rectangle::new(area.x(), area.y(), Size2d::new(width, position)),
+                Rectangle::new(
+                    area.x(),
+                    area.y() + position,
+                    Size2d::new(width, height - position),
+                ),
+            )
+        };
+
+        let first = self.split(random, map, first_area);
+        let second = self.split(random, map, second_area);
+
+        connect(random, map, &first, &second);
+
+        BspTree::Split {
+            area,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    fn calculate_split(&self, random: &mut Random, length: u32) -> u32 {
+        let min = self.min_leaf_size.max(length * self.split_ratio / 100);
+        let max = length - min;
+
+        if min > max {
+            return length / 2;
+        }
+
+        random.gen_range(min, max + 1)
+    }
+
+    fn create_leaf(&self, random: &mut Random, map: &mut OccupancyMap, area: Rectangle) -> BspTree {
+        let (x, width) = self.place_room(random, area.x(), area.size().width());
+        let (y, height) = self.place_room(random, area.y(), area.size().height());
+        let room = Rectangle::new(x, y, Size2d::new(width, height));
+
+        map.fill_rectangle(x, y, width, height, false);
+
+        BspTree::Leaf { area, room }
+    }
+
+    /// Places a room along one axis with a margin of 1 tile on both sides.
+    ///
+    /// Leaves without space for the margin get a room of 1 tile in their middle.
+    fn place_room(&self, random: &mut Random, start: u32, length: u32) -> (u32, u32) {
+        if length < 3 {
+            return (start + length / 2, length.min(1));
+        }
+
+        let available = length.saturating_sub(2).max(1);
+        let size = random.gen_range(self.min_room_size.min(available), available + 1);
+        let offset = random.gen_range(0, available - size + 1);
+
+        (start + 1 + offset, size)
+    }
+}
+
+/// Connects a random room of each sibling with a corridor
+fn connect(random: &mut Random, map: &mut OccupancyMap, first: &BspTree, second: &BspTree) {
+    let [start_x, start_y] = pick_room(random, first).center();
+    let [end_x, end_y] = pick_room(random, second).center();
+
+    let corner_x = if random.check_percentage(50) {
+        start_x
+    } else {
+        end_x
+    };
+    let corner_y = if corner_x == start_x { end_y } else { start_y };
+
+    carve_line(map, start_x, start_y, corner_x, corner_y);
+    carve_line(map, corner_x, corner_y, end_x, end_y);
+}
+
+fn pick_room(random: &mut Random, tree: &BspTree) -> Rectangle {
+    let rooms = tree.get_rooms();
+    rooms[random.gen_range(0, rooms.len() as u32) as usize]
+}
+
+/// Carves a horizontal or vertical line
+fn carve_line(map: &mut OccupancyMap, start_x: u32, start_y: u32, end_x: u32, end_y: u32) {
+    let x = start_x.min(end_x);
+    let y = start_y.min(end_y);
+    let width = start_x.max(end_x) - x + 1;
+    let height = start_y.max(end_y) - y + 1;
+
+    map.fill_rectangle(x, y, width, height, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::pathfinding::a_star::AStar;
+    use crate::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+
+    #[test]
+    fn test_leaves() {
+        let generator = BspGenerator::default();
+        let (tree, _map) = generator.generate(Size2d::new(60, 40), 5);
+
+        assert!(tree.get_depth() > 1);
+        assert_leaves(&tree);
+    }
+
+    fn assert_leaves(tree: &BspTree) {
+        match tree {
+            BspTree::Leaf { area, room } => {
+                assert!(area.size().width() >= 6 && area.size().width() <= 16);
+                assert!(area.size().height() >= 6 && area.size().height() <= 16);
+                assert!(room.x() > area.x() && room.end_x() < area.end_x());
+                assert!(room.y() > area.y() && room.end_y() < area.end_y());
+                assert!(room.size().width() >= 3 && room.size().height() >= 3);
+            }
+            BspTree::Split {
+                area,
+                first,
+                second,
+            } => {
+                let first_area = first.get_area();
+                let second_area = second.get_area();
+                assert_eq!(
+                    first_area.size().get_tiles() + second_area.size().get_tiles(),
+                    area.size().get_tiles()
+                );
+                assert_leaves(first);
+                assert_leaves(second);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rooms_are_connected() {
+        let size = Size2d::new(60, 40);
+        let (tree, map) = BspGenerator::default().generate(size, 9);
+        let rooms = tree.get_rooms();
+        let [start_x, start_y] = rooms[0].center();
+        let start = size.to_index(start_x, start_y);
+        let algorithm = AStar::default();
+
+        for room in rooms.iter().skip(1) {
+            let [goal_x, goal_y] = room.center();
+            let result = algorithm.find(&map, start, size.to_index(goal_x, goal_y));
+
+            match result {
+                PathfindingResult::Path { .. } => {}
+                _ => panic!("Room {:?} is not connected: {:?}", room, result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_tiny_maps() {
+        let generator = BspGenerator::default();
+
+        for (width, height) in &[(0, 0), (1, 1), (2, 1), (2, 2), (3, 3)] {
+            let size = Size2d::new(*width, *height);
+            let (tree, map) = generator.generate(size, 1);
+
+            for room in tree.get_rooms() {
+                assert!(room.end_x() <= *width && room.end_y() <= *height);
+            }
+            assert_eq!(map.is_occupied.len(), size.get_tiles());
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let generator = BspGenerator::new(0, 0, 0, 0);
+        let (tree, _map) = generator.generate(Size2d::new(8, 6), 2);
+
+        assert_eq!(tree.get_rooms().len(), 48);
+    }
+
+    #[test]
+    fn test_same_seed() {
+        let size = Size2d::new(50, 30);
+        let generator = BspGenerator::default();
+
+        assert_eq!(generator.generate(size, 3).0, generator.generate(size, 3).0);
+    }
+}
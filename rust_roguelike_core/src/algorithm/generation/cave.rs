@@ -0,0 +1,187 @@
+use crate::algorithm::connectivity::Regions;
+use crate::math::graph::occupancy::OccupancyMap;
+use crate::math::graph::Graph;
+use crate::math::random::Random;
+use crate::math::size2d::Size2d;
+
+/// Generates caves with a cellular automaton.
+///
+/// The map is filled with random noise and then smoothed for several iterations.
+/// Afterwards all free regions except the largest one are filled.
+///
+/// See [RogueBasin](http://roguebasin.com/index.php/Cellular_Automata_Method_for_Generating_Random_Cave-Like_Levels)
+#[derive(Clone, Copy, Debug)]
+pub struct CaveGenerator {
+    fill_percentage: u32,
+    iterations: u32,
+    birth_limit: u32,
+    survival_limit: u32,
+}
+
+impl Default for CaveGenerator {
+    fn default() -> Self {
+        CaveGenerator::new(45, 4, 5, 4)
+    }
+}
+
+impl CaveGenerator {
+    /// Creates a new CaveGenerator
+    ///
+    /// * `fill_percentage` - the chance of a tile to be occupied after the initial noise
+    /// * `iterations` - how often the rules are applied
+    /// * `birth_limit` - a free tile becomes occupied with at least this many occupied neighbors
+    /// * `survival_limit` - an occupied tile stays occupied with at least this many occupied neighbors
+    pub fn new(
+        fill_percentage: u32,
+        iterations: u32,
+        birth_limit: u32,
+        survival_limit: u32,
+    ) -> CaveGenerator {
+        CaveGenerator {
+            fill_percentage,
+            iterations,
+            birth_limit,
+            survival_limit,
+        }
+    }
+
+    /// Generates a cave of the desired size with a border
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::generation::cave::CaveGenerator;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let generator = CaveGenerator::default();
+    /// let size = Size2d::new(20, 10);
+    ///
+    /// assert_eq!(generator.generate(size, 42).is_occupied, generator.generate(size, 42).is_occupied);
+    /// ```
+    pub fn generate(&self, size: Size2d, seed: u64) -> OccupancyMap {
+        let mut map = OccupancyMap::new(size, false);
+
+        if size.get_tiles() == 0 {
+            return map;
+        }
+
+        let mut random = Random::new(seed);
+
+        for index in 0..size.get_tiles() {
+            map.set_node(index, random.check_percentage(self.fill_percentage));
+        }
+
+        map.add_border();
+
+        for _ in 0..self.iterations {
+            map = self.apply_rules(&map, size);
+        }
+
+        remove_disconnected_regions(&mut map);
+
+        map
+    }
+
+    fn apply_rules(&self, map: &OccupancyMap, size: Size2d) -> OccupancyMap {
+        let mut result = OccupancyMap::new(size, true);
+
+        for y in 1..size.height().saturating_sub(1) {
+            for x in 1..size.width().saturating_sub(1) {
+                let index = size.to_index(x, y);
+                let neighbors = count_occupied_neighbors(map, size, x, y);
+                let is_occupied = if map.is_occupied[index] {
+                    neighbors >= self.survival_limit
+                } else {
+                    neighbors >= self.birth_limit
+                };
+                result.set_node(index, is_occupied);
+            }
+        }
+
+        result
+    }
+}
+
+/// Counts the occupied tiles among the 8 neighbors of a tile
+fn count_occupied_neighbors(map: &OccupancyMap, size: Size2d, x: u32, y: u32) -> u32 {
+    let mut count = 0;
+
+    for ny in (y - 1)..=(y + 1) {
+        for nx in (x - 1)..=(x + 1) {
+            if (nx != x || ny != y) && map.is_occupied[size.to_index(nx, ny)] {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Fills all free regions except the largest one
+fn remove_disconnected_regions(map: &mut OccupancyMap) {
+    let regions = Regions::new(map);
+    let largest = regions.get_largest();
+
+    for index in 0..map.get_size() {
+        if let Some(region) = regions.get_region(index) {
+            if Some(region) != largest {
+                map.set_node(index, true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_border_is_occupied() {
+        let size = Size2d::new(30, 20);
+        let map = CaveGenerator::default().generate(size, 3);
+
+        for x in 0..size.width() {
+            assert!(map.is_occupied[size.to_index(x, 0)]);
+            assert!(map.is_occupied[size.to_index(x, size.height() - 1)]);
+        }
+
+        for y in 0..size.height() {
+            assert!(map.is_occupied[size.to_index(0, y)]);
+            assert!(map.is_occupied[size.to_index(size.width() - 1, y)]);
+        }
+    }
+
+    #[test]
+    fn test_empty_size() {
+        let generator = CaveGenerator::default();
+
+        assert!(generator
+            .generate(Size2d::new(0, 5), 1)
+            .is_occupied
+            .is_empty());
+        assert!(generator
+            .generate(Size2d::new(5, 0), 1)
+            .is_occupied
+            .is_empty());
+        assert!(generator.generate(Size2d::new(1, 1), 1).is_occupied[0]);
+    }
+
+    #[test]
+    fn test_only_one_region() {
+        let size = Size2d::new(40, 30);
+
+        for seed in 0..10 {
+            let map = CaveGenerator::default().generate(size, seed);
+
+            assert_eq!(Regions::new(&map).get_count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_different_seeds() {
+        let size = Size2d::new(40, 30);
+        let generator = CaveGenerator::default();
+
+        assert_ne!(
+            generator.generate(size, 1).is_occupied,
+            generator.generate(size, 2).is_occupied
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod bsp;
+pub mod cave;
+pub mod wfc;
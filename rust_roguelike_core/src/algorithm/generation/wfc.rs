@@ -0,0 +1,663 @@
+use crate::algorithm::generation::wfc::WfcResult::*;
+use crate::math::random::Random;
+use crate::math::size2d::Size2d;
+use std::collections::HashMap;
+
+/// The offsets to the 4 neighbors of a cell: north, east, south & west
+const DIRECTIONS: [[i32; 2]; 4] = [[0, 1], [1, 0], [0, -1], [-1, 0]];
+
+/// The result of the [`WfcGenerator`]
+#[derive(Debug, PartialEq, Eq)]
+pub enum WfcResult {
+    /// The generated tile ids of the output
+    Generated { tiles: Vec<usize> },
+    /// The output is smaller than the patterns
+    InvalidSize,
+    /// A fixed tile is outside the output or contradicts the patterns
+    InvalidConstraint,
+    /// No valid output was found within the allowed number of backtracks
+    Contradiction,
+}
+
+/// Forces the tile of the output at `index`
+#[derive(Clone, Copy, Debug)]
+pub struct FixedTile {
+    pub index: usize,
+    pub tile: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Pattern {
+    tiles: Vec<usize>,
+    weight: u32,
+}
+
+/// The NxN patterns learned from a sample grid of tile ids.
+///
+/// See the overlapping model of [Wave Function Collapse](https://github.com/mxgmn/WaveFunctionCollapse).
+#[derive(Clone, Debug)]
+pub struct WfcModel {
+    pattern_size: u32,
+    patterns: Vec<Pattern>,
+    /// Is pattern b compatible with pattern a in a direction? Indexed by `(a * 4 + direction) * n + b`.
+    compatible: Vec<bool>,
+}
+
+impl WfcModel {
+    /// Learns all patterns of the sample
+    ///
+    /// Returns `None`, if the sample is smaller than the patterns or has the wrong number of tiles.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::generation::wfc::WfcModel;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let sample = vec![0, 1, 1, 0];
+    ///
+    /// assert_eq!(WfcModel::new(Size2d::new(2, 2), &sample, 2, true).unwrap().get_pattern_count(), 2);
+    /// assert_eq!(WfcModel::new(Size2d::new(2, 2), &sample, 2, false).unwrap().get_pattern_count(), 1);
+    /// assert!(WfcModel::new(Size2d::new(2, 2), &sample, 3, false).is_none());
+    /// assert!(WfcModel::new(Size2d::new(3, 2), &sample, 2, false).is_none());
+    /// ```
+    pub fn new(
+        sample_size: Size2d,
+        sample: &[usize],
+        pattern_size: u32,
+        periodic_input: bool,
+    ) -> Option<WfcModel> {
+        if pattern_size == 0
+            || sample.len() != sample_size.get_tiles()
+            || sample_size.width() < pattern_size
+            || sample_size.height() < pattern_size
+        {
+            return None;
+        }
+
+        let (end_x, end_y) = if periodic_input {
+            (sample_size.width(), sample_size.height())
+        } else {
+            (
+                sample_size.width() - pattern_size + 1,
+                sample_size.height() - pattern_size + 1,
+            )
+        };
+
+        let mut patterns: Vec<Pattern> = Vec::new();
+        let mut lookup: HashMap<Vec<usize>, usize> = HashMap::new();
+
+        for y in 0..end_y {
+            for x in 0..end_x {
+                let tiles = extract_pattern(sample_size, sample, pattern_size, x, y);
+
+                if let Some(&index) = lookup.get(&tiles) {
+                    patterns[index].weight += 1;
+                } else {
+                    lookup.insert(tiles.clone(), patterns.len());
+                    patterns.push(Pattern { tiles, weight: 1 });
+                }
+            }
+        }
+
+        let compatible = calculate_compatibility(&patterns, pattern_size);
+
+        Some(WfcModel {
+            pattern_size,
+            patterns,
+            compatible,
+        })
+    }
+
+    /// Returns the number of unique patterns
+    pub fn get_pattern_count(&self) -> usize {
+        self.patterns.len()
+    }
+
+    fn is_compatible(&self, a: usize, direction: usize, b: usize) -> bool {
+        self.compatible[(a * 4 + direction) * self.patterns.len() + b]
+    }
+}
+
+fn extract_pattern(
+    size: Size2d,
+    sample: &[usize],
+    pattern_size: u32,
+    x: u32,
+    y: u32,
+) -> Vec<usize> {
+    let mut tiles = Vec::with_capacity((pattern_size * pattern_size) as usize);
+
+    for dy in 0..pattern_size {
+        for dx in 0..pattern_size {
+            let index = size.to_index((x + dx) % size.width(), (y + dy) % size.height());
+            tiles.push(sample[index]);
+        }
+    }
+
+    tiles
+}
+
+/// Pattern b is compatible with pattern a, if they agree after shifting b in a direction
+fn calculate_compatibility(patterns: &[Pattern], pattern_size: u32) -> Vec<bool> {
+    let n = patterns.len();
+    let size = pattern_size as i32;
+    let mut compatible = vec![false; n * 4 * n];
+
+    for (a, pattern_a) in patterns.iter().enumerate() {
+        for (direction, [dx, dy]) in DIRECTIONS.iter().enumerate() {
+            for (b, pattern_b) in patterns.iter().enumerate() {
+                let mut agrees = true;
+
+                for y in 0.max(*dy)..size.min(size + dy) {
+                    for x in 0.max(*dx)..size.min(size + dx) {
+                        let tile_a = pattern_a.tiles[(y * size + x) as usize];
+                        let tile_b = pattern_b.tiles[((y - dy) * size + x - dx) as usize];
+                        agrees &= tile_a == tile_b;
+                    }
+                }
+
+                compatible[(a * 4 + direction) * n + b] = agrees;
+            }
+        }
+    }
+
+    compatible
+}
+
+/// The possible patterns of each cell
+#[derive(Clone, Debug)]
+struct Wave {
+    is_possible: Vec<bool>,
+    counts: Vec<usize>,
+    /// The indices of all bans in order, so they can be undone when backtracking
+    trail: Vec<usize>,
+}
+
+impl Wave {
+    fn new(cells: usize, patterns: usize) -> Self {
+        Wave {
+            is_possible: vec![true; cells * patterns],
+            counts: vec![patterns; cells],
+            trail: Vec::new(),
+        }
+    }
+
+    /// Undoes all bans after the first `length` ones
+    fn undo(&mut self, length: usize, patterns: usize) {
+        for index in self.trail.drain(length..) {
+            self.is_possible[index] = true;
+            self.counts[index / patterns] += 1;
+        }
+    }
+}
+
+/// Synthesizes outputs of any size from a [`WfcModel`].
+#[derive(Clone, Copy, Debug)]
+pub struct WfcGenerator {
+    periodic_output: bool,
+    max_backtracks: u32,
+}
+
+impl Default for WfcGenerator {
+    fn default() -> Self {
+        WfcGenerator::new(false, 100)
+    }
+}
+
+impl WfcGenerator {
+    /// Creates a new WfcGenerator
+    ///
+    /// * `periodic_output` - the output wraps around at its edges
+    /// * `max_backtracks` - how often a contradiction is resolved by undoing the last choice
+    pub fn new(periodic_output: bool, max_backtracks: u32) -> WfcGenerator {
+        WfcGenerator {
+            periodic_output,
+            max_backtracks,
+        }
+    }
+
+    /// Generates an output of the desired size, which only contains patterns of the model
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::generation::wfc::{FixedTile, WfcGenerator, WfcModel, WfcResult};
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let sample = vec![0, 1, 1, 0];
+    /// let model = WfcModel::new(Size2d::new(2, 2), &sample, 2, true).unwrap();
+    /// let generator = WfcGenerator::default();
+    /// let fixed_tiles = [FixedTile { index: 0, tile: 1 }];
+    ///
+    /// assert_eq!(generator.generate(&model, Size2d::new(3, 2), &fixed_tiles, 42),
+    ///            WfcResult::Generated {
+    ///              tiles: vec![1, 0, 1, 0, 1, 0],
+    ///            });
+    /// ```
+    pub fn generate(
+        &self,
+        model: &WfcModel,
+        size: Size2d,
+        fixed_tiles: &[FixedTile],
+        seed: u64,
+    ) -> WfcResult {
+        let wave_size = match self.get_wave_size(model, size) {
+            Some(wave_size) => wave_size,
+            None => return InvalidSize,
+        };
+
+        let mut random = Random::new(seed);
+        let mut wave = Wave::new(wave_size.get_tiles(), model.get_pattern_count());
+
+        for fixed_tile in fixed_tiles {
+            if fixed_tile.index >= size.get_tiles() {
+                return InvalidConstraint;
+            }
+
+            let changed = self.apply_fixed_tile(model, size, wave_size, &mut wave, *fixed_tile);
+
+            if !self.propagate(model, wave_size, &mut wave, changed) {
+                return InvalidConstraint;
+            }
+        }
+
+        // The length of the trail before each choice, so it can be undone
+        let mut history: Vec<(usize, usize, usize)> = Vec::new();
+        let mut backtracks = 0;
+
+        while let Some(cell) = find_lowest_entropy(model, &wave, &mut random) {
+            let pattern = choose_pattern(model, &wave, cell, &mut random);
+
+            history.push((wave.trail.len(), cell, pattern));
+            collapse(model, &mut wave, cell, pattern);
+            let mut is_valid = self.propagate(model, wave_size, &mut wave, vec![cell]);
+
+            while !is_valid {
+                backtracks += 1;
+
+                if backtracks > self.max_backtracks {
+                    return Contradiction;
+                }
+
+                match history.pop() {
+                    None => return Contradiction,
+                    Some((length, cell, pattern)) => {
+                        wave.undo(length, model.get_pattern_count());
+                        ban(model, &mut wave, cell, pattern);
+                        is_valid = wave.counts[cell] > 0
+                            && self.propagate(model, wave_size, &mut wave, vec![cell]);
+                    }
+                }
+            }
+        }
+
+        Generated {
+            tiles: self.create_tiles(model, size, wave_size, &wave),
+        }
+    }
+
+    /// Returns the number of pattern positions along each axis
+    fn get_wave_size(&self, model: &WfcModel, size: Size2d) -> Option<Size2d> {
+        if self.periodic_output {
+            if size.get_tiles() == 0 {
+                return None;
+            }
+            return Some(size);
+        }
+
+        let n = model.pattern_size;
+
+        if size.width() < n || size.height() < n {
+            return None;
+        }
+
+        Some(Size2d::new(size.width() - n + 1, size.height() - n + 1))
+    }
+
+    /// Bans all patterns covering the fixed tile with a different tile and returns the changed cells
+    fn apply_fixed_tile(
+        &self,
+        model: &WfcModel,
+        size: Size2d,
+        wave_size: Size2d,
+        wave: &mut Wave,
+        fixed_tile: FixedTile,
+    ) -> Vec<usize> {
+        let [x, y] = size.to_point(fixed_tile.index);
+        let n = model.pattern_size;
+        let mut changed = Vec::new();
+
+        for dy in 0..n {
+            for dx in 0..n {
+                let cell = if self.periodic_output {
+                    let cell_x = (x + size.width() - dx % size.width()) % size.width();
+                    let cell_y = (y + size.height() - dy % size.height()) % size.height();
+                    wave_size.to_index(cell_x, cell_y)
+                } else if x >= dx
+                    && y >= dy
+                    && x - dx < wave_size.width()
+                    && y - dy < wave_size.height()
+                {
+                    wave_size.to_index(x - dx, y - dy)
+                } else {
+                    continue;
+                };
+
+                for (index, pattern) in model.patterns.iter().enumerate() {
+                    if pattern.tiles[(dy * n + dx) as usize] != fixed_tile.tile {
+                        ban(model, wave, cell, index);
+                    }
+                }
+
+                changed.push(cell);
+            }
+        }
+
+        changed
+    }
+
+    fn get_neighbor(&self, wave_size: Size2d, cell: usize, direction: usize) -> Option<usize> {
+        let [x, y] = wave_size.to_point(cell);
+        let [dx, dy] = DIRECTIONS[direction];
+        let width = wave_size.width() as i32;
+        let height = wave_size.height() as i32;
+        let mut x = x as i32 + dx;
+        let mut y = y as i32 + dy;
+
+        if self.periodic_output {
+            x = (x + width) % width;
+            y = (y + height) % height;
+        } else if x < 0 || y < 0 || x >= width || y >= height {
+            return None;
+        }
+
+        Some(wave_size.to_index(x as u32, y as u32))
+    }
+
+    /// Removes all patterns without support from a neighbor.
+    ///
+    /// Returns false, if a cell has no possible pattern left.
+    fn propagate(
+        &self,
+        model: &WfcModel,
+        wave_size: Size2d,
+        wave: &mut Wave,
+        mut changed: Vec<usize>,
+    ) -> bool {
+        let n = model.get_pattern_count();
+
+        while let Some(cell) = changed.pop() {
+            if wave.counts[cell] == 0 {
+                return false;
+            }
+
+            for direction in 0..DIRECTIONS.len() {
+                let neighbor = match self.get_neighbor(wave_size, cell, direction) {
+                    Some(neighbor) => neighbor,
+                    None => continue,
+                };
+                let mut has_changed = false;
+
+                for b in 0..n {
+                    if !wave.is_possible[neighbor * n + b] {
+                        continue;
+                    }
+
+                    let is_supported = (0..n).any(|a| {
+                        wave.is_possible[cell * n + a] && model.is_compatible(a, direction, b)
+                    });
+
+                    if !is_supported {
+                        ban(model, wave, neighbor, b);
+                        has_changed = true;
+                    }
+                }
+
+                if wave.counts[neighbor] == 0 {
+                    return false;
+                } else if has_changed {
+                    changed.push(neighbor);
+                }
+            }
+        }
+
+        true
+    }
+
+    fn create_tiles(
+        &self,
+        model: &WfcModel,
+        size: Size2d,
+        wave_size: Size2d,
+        wave: &Wave,
+    ) -> Vec<usize> {
+        let n = model.get_pattern_count();
+        let mut tiles = Vec::with_capacity(size.get_tiles());
+
+        for index in 0..size.get_tiles() {
+            let [x, y] = size.to_point(index);
+            let cell_x = x.min(wave_size.width() - 1);
+            let cell_y = y.min(wave_size.height() - 1);
+            let cell = wave_size.to_index(cell_x, cell_y);
+            let pattern = (0..n)
+                .find(|pattern| wave.is_possible[cell * n + pattern])
+                .unwrap_or_default();
+            let offset = (y - cell_y) * model.pattern_size + x - cell_x;
+
+            tiles.push(model.patterns[pattern].tiles[offset as usize]);
+        }
+
+        tiles
+    }
+}
+
+fn ban(model: &WfcModel, wave: &mut Wave, cell: usize, pattern: usize) {
+    let index = cell * model.get_pattern_count() + pattern;
+
+    if wave.is_possible[index] {
+        wave.is_possible[index] = false;
+        wave.counts[cell] -= 1;
+        wave.trail.push(index);
+    }
+}
+
+fn collapse(model: &WfcModel, wave: &mut Wave, cell: usize, chosen: usize) {
+    for pattern in 0..model.get_pattern_count() {
+        if pattern != chosen {
+            ban(model, wave, cell, pattern);
+        }
+    }
+}
+
+/// Returns the undecided cell with the lowest entropy, which is chosen randomly between ties
+fn find_lowest_entropy(model: &WfcModel, wave: &Wave, random: &mut Random) -> Option<usize> {
+    let n = model.get_pattern_count();
+    let mut best: Option<(usize, f64)> = None;
+
+    for (cell, count) in wave.counts.iter().enumerate() {
+        if *count <= 1 {
+            continue;
+        }
+
+        let mut sum = 0.0;
+        let mut sum_of_logs = 0.0;
+
+        for (pattern, data) in model.patterns.iter().enumerate() {
+            if wave.is_possible[cell * n + pattern] {
+                let weight = data.weight as f64;
+                sum += weight;
+                sum_of_logs += weight * weight.ln();
+            }
+        }
+
+        let noise = random.next_u32() as f64 / u32::MAX as f64 * 1e-6;
+        let entropy = sum.ln() - sum_of_logs / sum + noise;
+
+        let is_lower = match best {
+            None => true,
+            Some((_, lowest)) => entropy < lowest,
+        };
+
+        if is_lower {
+            best = Some((cell, entropy));
+        }
+    }
+
+    best.map(|(cell, _)| cell)
+}
+
+/// Chooses a possible pattern of the cell weighted by its frequency in the sample
+fn choose_pattern(model: &WfcModel, wave: &Wave, cell: usize, random: &mut Random) -> usize {
+    let n = model.get_pattern_count();
+    let possible: Vec<usize> = (0..n)
+        .filter(|pattern| wave.is_possible[cell * n + pattern])
+        .collect();
+    let total: u32 = possible.iter().map(|p| model.patterns[*p].weight).sum();
+    let mut value = random.gen_range(0, total);
+
+    for pattern in &possible {
+        let weight = model.patterns[*pattern].weight;
+
+        if value < weight {
+            return *pattern;
+        }
+
+        value -= weight;
+    }
+
+    possible[possible.len() - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A room with walls (1) around a floor (0)
+    fn create_room_model(periodic_input: bool) -> WfcModel {
+        let sample = vec![
+            1, 1, 1, 1, 1, //
+            1, 0, 0, 0, 1, //
+            1, 0, 0, 0, 1, //
+            1, 0, 0, 0, 1, //
+            1, 1, 1, 1, 1, //
+        ];
+        WfcModel::new(Size2d::new(5, 5), &sample, 2, periodic_input).unwrap()
+    }
+
+    /// Checks that each 2x2 block of the output exists in the model
+    fn assert_only_known_patterns(model: &WfcModel, size: Size2d, tiles: &[usize]) {
+        for y in 0..(size.height() - 1) {
+            for x in 0..(size.width() - 1) {
+                let pattern = extract_pattern(size, tiles, 2, x, y);
+                assert!(model.patterns.iter().any(|p| p.tiles == pattern));
+            }
+        }
+    }
+
+    #[test]
+    fn test_learn_patterns() {
+        let model = create_room_model(false);
+
+        assert_eq!(model.get_pattern_count(), 9);
+        assert_eq!(model.patterns.iter().map(|p| p.weight).sum::<u32>(), 16);
+    }
+
+    #[test]
+    fn test_undo_bans() {
+        let model = create_room_model(false);
+        let mut wave = Wave::new(4, 9);
+        ban(&model, &mut wave, 1, 3);
+        let length = wave.trail.len();
+        collapse(&model, &mut wave, 2, 5);
+        ban(&model, &mut wave, 1, 4);
+        ban(&model, &mut wave, 1, 3);
+
+        assert_eq!(wave.counts, vec![9, 7, 1, 9]);
+
+        wave.undo(length, 9);
+
+        assert_eq!(wave.counts, vec![9, 8, 9, 9]);
+        assert_eq!(wave.trail, vec![12]);
+
+        wave.undo(0, 9);
+
+        assert!(wave.is_possible.iter().all(|is_possible| *is_possible));
+    }
+
+    #[test]
+    fn test_generate_non_periodic() {
+        let model = create_room_model(false);
+        let size = Size2d::new(12, 8);
+
+        match WfcGenerator::default().generate(&model, size, &[], 7) {
+            Generated { tiles } => {
+                assert_eq!(tiles.len(), size.get_tiles());
+                assert_only_known_patterns(&model, size, &tiles);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_generate_periodic() {
+        let model = create_room_model(true);
+        let size = Size2d::new(10, 10);
+        let generator = WfcGenerator::new(true, 100);
+
+        match generator.generate(&model, size, &[], 3) {
+            Generated { tiles } => assert_eq!(tiles.len(), size.get_tiles()),
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_fixed_tiles() {
+        let model = create_room_model(false);
+        let size = Size2d::new(9, 9);
+        let fixed_tiles = [
+            FixedTile { index: 40, tile: 0 },
+            FixedTile { index: 0, tile: 1 },
+        ];
+
+        match WfcGenerator::default().generate(&model, size, &fixed_tiles, 11) {
+            Generated { tiles } => {
+                assert_eq!(tiles[40], 0);
+                assert_eq!(tiles[0], 1);
+                assert_only_known_patterns(&model, size, &tiles);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_same_seed() {
+        let model = create_room_model(false);
+        let size = Size2d::new(10, 10);
+        let generator = WfcGenerator::default();
+
+        assert_eq!(
+            generator.generate(&model, size, &[], 5),
+            generator.generate(&model, size, &[], 5)
+        );
+    }
+
+    #[test]
+    fn test_invalid_size() {
+        let model = create_room_model(false);
+
+        assert_eq!(
+            WfcGenerator::default().generate(&model, Size2d::new(1, 5), &[], 0),
+            InvalidSize
+        );
+    }
+
+    #[test]
+    fn test_invalid_constraint() {
+        let model = create_room_model(false);
+        let size = Size2d::new(4, 4);
+
+        assert_eq!(
+            WfcGenerator::default().generate(&model, size, &[FixedTile { index: 0, tile: 5 }], 0),
+            InvalidConstraint
+        );
+        assert_eq!(
+            WfcGenerator::default().generate(&model, size, &[FixedTile { index: 16, tile: 0 }], 0),
+            InvalidConstraint
+        );
+    }
+}
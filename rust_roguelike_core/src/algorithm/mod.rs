@@ -1 +1,3 @@
+pub mod connectivity;
+pub mod generation;
 pub mod pathfinding;
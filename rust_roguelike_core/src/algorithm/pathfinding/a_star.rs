@@ -1,17 +1,62 @@
+use crate::algorithm::pathfinding::context::{OpenNode, SearchContext};
+use crate::algorithm::pathfinding::goal::Goal;
+use crate::algorithm::pathfinding::trace::{NoTracer, SearchTracer};
 use crate::algorithm::pathfinding::PathfindingResult::*;
-use crate::algorithm::pathfinding::{CostCalculator, PathfindingAlgorithm, PathfindingResult};
+use crate::algorithm::pathfinding::{
+    Budget, CostCalculator, PathfindingAlgorithm, PathfindingResult,
+};
 use crate::math::distance::DistanceCalculator;
 use crate::math::graph::Graph;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
 use std::fmt::Debug;
 
+/// The heuristic weight of the normal A*, which always finds the cheapest path
+pub const DEFAULT_HEURISTIC_WEIGHT: u32 = 100;
+
+/// How AStar chooses between open nodes with the same total cost
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TieBreaking {
+    /// Prefers the node with the highest cost so far, which is usually closer to the goal
+    #[default]
+    HighestCost,
+    /// Prefers the node with the lowest cost so far
+    LowestCost,
+    /// Prefers the node, which was added first
+    FirstAdded,
+    /// Prefers the node, which was added last
+    LastAdded,
+}
+
+impl TieBreaking {
+    /// Returns the secondary priority of an open node, where lower values are expanded first
+    pub(crate) fn get_tie_breaker(&self, cost: u32, counter: u64) -> u64 {
+        match self {
+            TieBreaking::HighestCost => u64::from(u32::MAX - cost),
+            TieBreaking::LowestCost => u64::from(cost),
+            TieBreaking::FirstAdded => counter,
+            TieBreaking::LastAdded => u64::MAX - counter,
+        }
+    }
+}
+
 /// The A* search algorithm
 ///
 /// See [Wikipedia](https://en.wikipedia.org/wiki/A*_search_algorithm)
-#[derive(Default)]
 pub struct AStar {
     distance_calculator: DistanceCalculator,
+    budget: Budget,
+    heuristic_weight: u32,
+    tie_breaking: TieBreaking,
+}
+
+impl Default for AStar {
+    fn default() -> Self {
+        AStar::new(
+            DistanceCalculator::default(),
+            Budget::default(),
+            DEFAULT_HEURISTIC_WEIGHT,
+            TieBreaking::default(),
+        )
+    }
 }
 
 impl<N, E> PathfindingAlgorithm<N, E> for AStar {
@@ -30,6 +75,7 @@ impl<N, E> PathfindingAlgorithm<N, E> for AStar {
     /// assert_eq!(algorithm.find(&map, 6, 8),
     ///            PathfindingResult::Path {
     ///              indices: vec![11, 12, 13, 8],
+    ///              cost: 4,
     ///            });
     /// ```
     fn find<G>(&self, graph: &G, start: usize, goal: usize) -> PathfindingResult
@@ -37,136 +83,331 @@ impl<N, E> PathfindingAlgorithm<N, E> for AStar {
         G: Graph<N, E> + CostCalculator<E>,
         E: Debug,
     {
-        println!("Find a path from {} to {}", start, goal);
+        let mut context = SearchContext::default();
+        self.find_with_context(&mut context, graph, start, goal)
+    }
+}
+
+impl AStar {
+    /// Creates a new AStar with a [`Budget`] limiting each search
+    ///
+    /// * `heuristic_weight` - the heuristic is multiplied by this percentage.
+    ///   Weights above [`DEFAULT_HEURISTIC_WEIGHT`] expand fewer nodes,
+    ///   but the paths can be up to `heuristic_weight / 100` times more expensive than the cheapest one.
+    /// * `tie_breaking` - the order of open nodes with the same total cost
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::distance::DistanceCalculator;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::a_star::{AStar, TieBreaking};
+    ///# use rust_roguelike_core::algorithm::pathfinding::{Budget, PathfindingAlgorithm, PathfindingResult};
+    /// let mut map = OccupancyMap::new(Size2d::new(5, 5), false);
+    /// map.add_border();
+    /// let algorithm = AStar::new(DistanceCalculator::Manhattan, Budget::default(), 100, TieBreaking::LowestCost);
+    ///
+    /// assert_eq!(algorithm.find(&map, 6, 18),
+    ///            PathfindingResult::Path {
+    ///              indices: vec![11, 16, 17, 18],
+    ///              cost: 4,
+    ///            });
+    /// ```
+    pub fn new(
+        distance_calculator: DistanceCalculator,
+        budget: Budget,
+        heuristic_weight: u32,
+        tie_breaking: TieBreaking,
+    ) -> Self {
+        AStar {
+            distance_calculator,
+            budget,
+            heuristic_weight,
+            tie_breaking,
+        }
+    }
+
+    /// Finds the shortest available path like [`PathfindingAlgorithm::find`],
+    /// but reuses the storage of the context to avoid allocations.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::graph::Graph;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::a_star::AStar;
+    ///# use rust_roguelike_core::algorithm::pathfinding::context::SearchContext;
+    ///# use rust_roguelike_core::algorithm::pathfinding::PathfindingResult;
+    /// let mut map = OccupancyMap::new(Size2d::new(5, 4), false);
+    /// map.add_border();
+    /// let algorithm = AStar::default();
+    /// let mut context = SearchContext::new(map.get_size());
+    ///
+    /// for _monster in 0..3 {
+    ///     assert_eq!(algorithm.find_with_context(&mut context, &map, 6, 13),
+    ///                PathfindingResult::Path {
+    ///                  indices: vec![11, 12, 13],
+    ///                  cost: 3,
+    ///                });
+    /// }
+    /// ```
+    pub fn find_with_context<N, E, G>(
+        &self,
+        context: &mut SearchContext,
+        graph: &G,
+        start: usize,
+        goal: usize,
+    ) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+        E: Debug,
+    {
+        self.find_with_tracer(context, graph, start, goal, &mut NoTracer)
+    }
+
+    /// Finds the shortest available path like [`AStar::find_with_context`]
+    /// and reports each step of the search to the tracer.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::a_star::AStar;
+    ///# use rust_roguelike_core::algorithm::pathfinding::context::SearchContext;
+    ///# use rust_roguelike_core::algorithm::pathfinding::trace::SearchRecorder;
+    ///# use rust_roguelike_core::algorithm::pathfinding::PathfindingResult;
+    /// let mut map = OccupancyMap::new(Size2d::new(5, 3), false);
+    /// map.add_border();
+    /// let algorithm = AStar::default();
+    /// let mut recorder = SearchRecorder::default();
+    ///
+    /// algorithm.find_with_tracer(&mut SearchContext::default(), &map, 6, 8, &mut recorder);
+    ///
+    /// assert_eq!(recorder.expanded, vec![6, 7]);
+    /// assert_eq!(recorder.pushed, vec![7, 8]);
+    /// assert_eq!(recorder.result, PathfindingResult::Path { indices: vec![7, 8], cost: 2 });
+    /// ```
+    pub fn find_with_tracer<N, E, G, T>(
+        &self,
+        context: &mut SearchContext,
+        graph: &G,
+        start: usize,
+        goal: usize,
+        tracer: &mut T,
+    ) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+        E: Debug,
+        T: SearchTracer + ?Sized,
+    {
+        tracer.on_start(start, goal);
+        let result = self.search(context, graph, start, &Goal::Node(goal), tracer);
+        tracer.on_result(&result);
+        result
+    }
+
+    /// Finds the cheapest path to whichever goal can be reached first.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::a_star::AStar;
+    ///# use rust_roguelike_core::algorithm::pathfinding::context::SearchContext;
+    ///# use rust_roguelike_core::algorithm::pathfinding::goal::Goal;
+    ///# use rust_roguelike_core::algorithm::pathfinding::PathfindingResult;
+    /// let mut map = OccupancyMap::new(Size2d::new(9, 3), false);
+    /// map.add_border();
+    /// let algorithm = AStar::default();
+    /// let mut context = SearchContext::default();
+    /// let items = [10, 15];
+    ///
+    /// assert_eq!(algorithm.find_goal(&mut context, &map, 13, &Goal::AnyOf(&items)),
+    ///            PathfindingResult::Path {
+    ///              indices: vec![14, 15],
+    ///              cost: 2,
+    ///            });
+    /// assert_eq!(algorithm.find_goal(&mut context, &map, 10, &Goal::WithinRange { target: 16, range: 2 }),
+    ///            PathfindingResult::Path {
+    ///              indices: vec![11, 12, 13, 14],
+    ///              cost: 4,
+    ///            });
+    /// ```
+    pub fn find_goal<N, E, G>(
+        &self,
+        context: &mut SearchContext,
+        graph: &G,
+        start: usize,
+        goal: &Goal,
+    ) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        self.search(context, graph, start, goal, &mut NoTracer)
+    }
+
+    fn search<N, E, G, T>(
+        &self,
+        context: &mut SearchContext,
+        graph: &G,
+        start: usize,
+        goal: &Goal,
+        tracer: &mut T,
+    ) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+        T: SearchTracer + ?Sized,
+    {
+        let size = graph.get_size();
 
-        if start == goal {
+        if start >= size {
+            return InvalidStart;
+        } else if goal.is_reached(graph, self.distance_calculator, start) {
             return GoalAlreadyReached;
         } else if !graph.is_valid(start) {
             return InvalidStart;
-        } else if !graph.is_valid(goal) {
+        } else if !goal.is_valid(graph) {
             return InvalidGoal;
         }
 
-        let mut open_nodes = BinaryHeap::new();
-        open_nodes.push(OpenNode::start(start));
+        context.prepare(size);
+
+        let start_node = context.get_or_insert(start, || {
+            goal.estimate(graph, self.distance_calculator, start)
+        });
+        start_node.cost = 0;
+        start_node.total_cost = 0;
+        let mut best = *start_node;
+        context.open_nodes.push(OpenNode::start(start));
+
+        let mut best_index = start;
+        let mut expanded_nodes = 0;
+        let mut pushed_nodes = 0;
+
+        while let Some(open_node) = context.open_nodes.pop() {
+            let node = match context.get(open_node.index) {
+                Some(node) if open_node.total_cost <= node.total_cost => *node,
+                _ => continue,
+            };
+
+            if goal.is_reached(graph, self.distance_calculator, open_node.index) {
+                return Path {
+                    indices: context.create_path(open_node.index),
+                    cost: node.cost,
+                };
+            } else if self
+                .budget
+                .max_nodes
+                .is_some_and(|max_nodes| expanded_nodes >= max_nodes)
+            {
+                return BudgetExceeded {
+                    indices: context.create_path(best_index),
+                    cost: best.cost,
+                };
+            }
 
-        let mut nodes: HashMap<usize, Node> = HashMap::new();
-        nodes.insert(start, Node::new(0, 0));
+            expanded_nodes += 1;
+            tracer.on_expand(open_node.index, node.cost);
 
-        while let Some(node) = open_nodes.pop() {
-            if node.index == goal {
-                return self.create_path(&nodes, goal);
+            if (node.heuristic, node.cost) < (best.heuristic, best.cost) {
+                best = node;
+                best_index = open_node.index;
             }
 
-            for neighbor in graph.get_neighbors(node.index) {
-                let neighbor_node = nodes.entry(neighbor.index).or_insert_with(|| {
-                    let heuristic =
-                        graph.get_distance(self.distance_calculator, neighbor.index, goal);
-                    Node::new(u32::MAX, heuristic)
-                });
+            for neighbor in graph.get_neighbors(open_node.index) {
+                if neighbor.index >= size || !graph.is_valid(neighbor.index) {
+                    continue;
+                }
 
-                let cost_to_neighbor = graph.calculate_cost(node.index, &neighbor);
-                let new_total_cost = node.total_cost + cost_to_neighbor + neighbor_node.heuristic;
+                let cost_to_neighbor = graph.calculate_cost(open_node.index, &neighbor);
+                let new_cost = node.cost.saturating_add(cost_to_neighbor);
 
-                if new_total_cost < neighbor_node.total_cost {
-                    neighbor_node.cost_from_previous = cost_to_neighbor;
+                if self
+                    .budget
+                    .max_cost
+                    .is_some_and(|max_cost| new_cost > max_cost)
+                {
+                    context.pending_nodes.push(neighbor.index);
+                    continue;
+                }
+
+                let neighbor_node = context.get_or_insert(neighbor.index, || {
+                    goal.estimate(graph, self.distance_calculator, neighbor.index)
+                });
+
+                if new_cost < neighbor_node.cost {
+                    let new_total_cost =
+                        new_cost.saturating_add(self.weight(neighbor_node.heuristic));
+                    let tie_breaker = self.tie_breaking.get_tie_breaker(new_cost, pushed_nodes);
+                    neighbor_node.cost = new_cost;
                     neighbor_node.total_cost = new_total_cost;
-                    neighbor_node.previous = Some(node.index);
-                    open_nodes.push(OpenNode::new(neighbor.index, neighbor_node.total_cost));
+                    neighbor_node.previous = Some(open_node.index);
+                    pushed_nodes += 1;
+                    tracer.on_push(neighbor.index, new_total_cost);
+                    context.open_nodes.push(OpenNode::new(
+                        neighbor.index,
+                        new_total_cost,
+                        tie_breaker,
+                    ));
                 }
             }
         }
 
-        PathfindingResult::NoPathFound
-    }
-}
-
-impl AStar {
-    /// Backtracks the path from the goal to the start node
-    fn create_path(&self, nodes: &HashMap<usize, Node>, goal: usize) -> PathfindingResult {
-        let mut current_node = nodes.get(&goal);
-        let mut current_index = goal;
-        let mut indices = Vec::new();
-
-        while let Some(node) = current_node {
-            indices.push(current_index);
-            current_node = node.previous.and_then(|i| {
-                current_index = i;
-                nodes.get(&i)
-            });
+        if self.is_reachable(context, graph, goal) {
+            return BudgetExceeded {
+                indices: context.create_path(best_index),
+                cost: best.cost,
+            };
         }
 
-        indices.pop();
-        indices.reverse();
-
-        PathfindingResult::Path { indices }
+        NoPathFound
     }
-}
 
-#[derive(Copy, Clone, Debug)]
-struct OpenNode {
-    index: usize,
-    total_cost: u32,
-}
+    /// Checks if the goal can be reached from the pending nodes, which exceeded the maximum cost.
+    ///
+    /// Otherwise the goal is unreachable and the budget didn't cut the search short.
+    fn is_reachable<N, E, G>(&self, context: &mut SearchContext, graph: &G, goal: &Goal) -> bool
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let size = graph.get_size();
 
-impl OpenNode {
-    fn new(index: usize, total_cost: u32) -> Self {
-        OpenNode { index, total_cost }
-    }
+        while let Some(index) = context.pending_nodes.pop() {
+            if !context.visit(index) {
+                continue;
+            } else if goal.is_reached(graph, self.distance_calculator, index) {
+                return true;
+            }
 
-    fn start(index: usize) -> Self {
-        OpenNode {
-            index,
-            total_cost: 0,
+            for neighbor in graph.get_neighbors(index) {
+                if neighbor.index < size && graph.is_valid(neighbor.index) {
+                    context.pending_nodes.push(neighbor.index);
+                }
+            }
         }
-    }
-}
-
-impl PartialEq for OpenNode {
-    fn eq(&self, other: &Self) -> bool {
-        self.index == other.index
-    }
-}
 
-impl Eq for OpenNode {}
-
-impl Ord for OpenNode {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.total_cost.cmp(&self.total_cost)
-    }
-}
-
-impl PartialOrd for OpenNode {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+        false
     }
-}
-
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
-struct Node {
-    cost_from_previous: u32,
-    heuristic: u32,
-    total_cost: u32,
-    previous: Option<usize>,
-}
 
-impl Node {
-    fn new(total_cost: u32, heuristic: u32) -> Self {
-        Node {
-            cost_from_previous: 0,
-            heuristic,
-            total_cost,
-            previous: None,
-        }
+    /// Applies the heuristic weight
+    fn weight(&self, heuristic: u32) -> u32 {
+        let weighted = u64::from(heuristic) * u64::from(self.heuristic_weight) / 100;
+        weighted.min(u64::from(u32::MAX)) as u32
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::graph::cost::CostMap;
+    use crate::math::graph::cost::IMPASSABLE;
     use crate::math::graph::occupancy::OccupancyMap;
+    use crate::math::random::Random;
     use crate::math::size2d::Size2d;
 
+    const TIE_BREAKINGS: [TieBreaking; 4] = [
+        TieBreaking::HighestCost,
+        TieBreaking::LowestCost,
+        TieBreaking::FirstAdded,
+        TieBreaking::LastAdded,
+    ];
+
     #[test]
     fn test_no_path_found() {
         let mut map = OccupancyMap::new(Size2d::new(5, 3), false);
@@ -203,4 +444,361 @@ mod tests {
 
         assert_eq!(algorithm.find(&map, 4, 0), InvalidGoal);
     }
+
+    #[test]
+    fn test_max_nodes() {
+        let mut map = OccupancyMap::new(Size2d::new(10, 3), false);
+        map.add_border();
+        let algorithm = AStar::new(
+            DistanceCalculator::Manhattan,
+            Budget::new(Some(3), None),
+            DEFAULT_HEURISTIC_WEIGHT,
+            TieBreaking::default(),
+        );
+
+        assert_eq!(
+            algorithm.find(&map, 11, 18),
+            BudgetExceeded {
+                indices: vec![12, 13],
+                cost: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_max_cost() {
+        let mut map = OccupancyMap::new(Size2d::new(10, 3), false);
+        map.add_border();
+        let algorithm = AStar::new(
+            DistanceCalculator::Manhattan,
+            Budget::new(None, Some(4)),
+            DEFAULT_HEURISTIC_WEIGHT,
+            TieBreaking::default(),
+        );
+
+        assert_eq!(
+            algorithm.find(&map, 11, 18),
+            BudgetExceeded {
+                indices: vec![12, 13, 14, 15],
+                cost: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn test_max_cost_with_unreachable_goal() {
+        let mut map = OccupancyMap::new(Size2d::new(10, 3), false);
+        map.add_border();
+        map.set_node(17, true);
+        let algorithm = AStar::new(
+            DistanceCalculator::Manhattan,
+            Budget::new(None, Some(4)),
+            DEFAULT_HEURISTIC_WEIGHT,
+            TieBreaking::default(),
+        );
+
+        assert_eq!(algorithm.find(&map, 11, 18), NoPathFound);
+
+        let mut context = SearchContext::default();
+
+        for _ in 0..2 {
+            assert_eq!(
+                algorithm.find_with_context(&mut context, &map, 11, 18),
+                NoPathFound
+            );
+            assert_eq!(
+                algorithm.find_with_context(&mut context, &map, 11, 16),
+                BudgetExceeded {
+                    indices: vec![12, 13, 14, 15],
+                    cost: 4,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_outside_graph() {
+        let map = OccupancyMap::new(Size2d::new(3, 3), false);
+        let algorithm = AStar::default();
+
+        assert_eq!(algorithm.find(&map, 9, 0), InvalidStart);
+        assert_eq!(algorithm.find(&map, 100, 100), InvalidStart);
+        assert_eq!(algorithm.find(&map, 0, 9), InvalidGoal);
+        assert_eq!(
+            algorithm.find_goal(
+                &mut SearchContext::default(),
+                &map,
+                0,
+                &Goal::AnyOf(&[20, 30])
+            ),
+            InvalidGoal
+        );
+    }
+
+    #[test]
+    fn test_max_cost_is_enough() {
+        let mut map = OccupancyMap::new(Size2d::new(10, 3), false);
+        map.add_border();
+        let algorithm = AStar::new(
+            DistanceCalculator::Manhattan,
+            Budget::new(None, Some(7)),
+            DEFAULT_HEURISTIC_WEIGHT,
+            TieBreaking::default(),
+        );
+
+        assert_eq!(
+            algorithm.find(&map, 11, 18),
+            Path {
+                indices: vec![12, 13, 14, 15, 16, 17, 18],
+                cost: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn test_reuse_context_for_different_graphs() {
+        let mut small_map = OccupancyMap::new(Size2d::new(3, 3), false);
+        small_map.add_border();
+        let mut large_map = OccupancyMap::new(Size2d::new(5, 5), false);
+        large_map.add_border();
+        let algorithm = AStar::default();
+        let mut context = SearchContext::default();
+
+        assert_eq!(
+            algorithm.find_with_context(&mut context, &large_map, 6, 18),
+            Path {
+                indices: vec![11, 16, 17, 18],
+                cost: 4,
+            }
+        );
+        assert_eq!(
+            algorithm.find_with_context(&mut context, &small_map, 4, 0),
+            InvalidGoal
+        );
+        assert_eq!(
+            algorithm.find_with_context(&mut context, &large_map, 18, 6),
+            Path {
+                indices: vec![13, 8, 7, 6],
+                cost: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn test_nearest_goal_by_cost() {
+        let mut map = CostMap::new(Size2d::new(5, 1), 1);
+        map.set_cost(1, 10);
+        let goals = [0, 4];
+        let algorithm = AStar::default();
+
+        assert_eq!(
+            algorithm.find_goal(&mut SearchContext::default(), &map, 1, &Goal::AnyOf(&goals)),
+            Path {
+                indices: vec![0],
+                cost: 1,
+            }
+        );
+        assert_eq!(
+            algorithm.find_goal(&mut SearchContext::default(), &map, 2, &Goal::AnyOf(&goals)),
+            Path {
+                indices: vec![3, 4],
+                cost: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_goals() {
+        let mut map = OccupancyMap::new(Size2d::new(5, 3), false);
+        map.add_border();
+        let goals = [0, 1];
+        let algorithm = AStar::default();
+
+        assert_eq!(
+            algorithm.find_goal(&mut SearchContext::default(), &map, 6, &Goal::AnyOf(&goals)),
+            InvalidGoal
+        );
+    }
+
+    #[test]
+    fn test_goal_predicate() {
+        let mut map = OccupancyMap::new(Size2d::new(6, 3), false);
+        map.add_border();
+        let is_exit = |index: usize| index > 9;
+        let algorithm = AStar::default();
+
+        assert_eq!(
+            algorithm.find_goal(
+                &mut SearchContext::default(),
+                &map,
+                7,
+                &Goal::Predicate(&is_exit)
+            ),
+            Path {
+                indices: vec![8, 9, 10],
+                cost: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_optimal_on_random_occupancy_maps() {
+        let size = Size2d::new(15, 10);
+
+        for seed in 0..200 {
+            let mut random = Random::new(seed);
+            let mut map = OccupancyMap::new(size, false);
+            map.add_random_nodes(&mut random, (seed % 5) as u32 * 10);
+            map.add_border();
+
+            let (start, goal) = choose_start_and_goal(&mut random, &map);
+            let optimal = find_cheapest_cost(&map, start, goal);
+
+            for tie_breaking in &TIE_BREAKINGS {
+                let algorithm = create(DEFAULT_HEURISTIC_WEIGHT, *tie_breaking);
+                let result = algorithm.find(&map, start, goal);
+
+                assert_eq!(get_cost(&map, start, &result), optimal, "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_optimal_on_random_cost_maps() {
+        let size = Size2d::new(12, 12);
+
+        for seed in 0..200 {
+            let mut random = Random::new(seed);
+            let mut map = CostMap::new(size, 1);
+
+            for index in 0..size.get_tiles() {
+                let cost = if random.check_percentage(15) {
+                    IMPASSABLE
+                } else {
+                    random.gen_range(1, 10)
+                };
+                map.set_cost(index, cost);
+            }
+
+            let (start, goal) = choose_start_and_goal(&mut random, &map);
+            let optimal = find_cheapest_cost(&map, start, goal);
+
+            for tie_breaking in &TIE_BREAKINGS {
+                let algorithm = create(DEFAULT_HEURISTIC_WEIGHT, *tie_breaking);
+                let result = algorithm.find(&map, start, goal);
+
+                assert_eq!(get_cost(&map, start, &result), optimal, "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_weighted_cost_is_bounded() {
+        let size = Size2d::new(12, 12);
+
+        for seed in 0..200 {
+            let mut random = Random::new(seed);
+            let mut map = CostMap::new(size, 1);
+
+            for index in 0..size.get_tiles() {
+                map.set_cost(index, random.gen_range(1, 5));
+            }
+
+            let (start, goal) = choose_start_and_goal(&mut random, &map);
+            let optimal = find_cheapest_cost(&map, start, goal).unwrap();
+            let result = create(250, TieBreaking::default()).find(&map, start, goal);
+            let cost = get_cost(&map, start, &result).unwrap();
+
+            assert!(cost >= optimal);
+            assert!(cost * 100 <= optimal * 250, "seed {}", seed);
+        }
+    }
+
+    fn create(heuristic_weight: u32, tie_breaking: TieBreaking) -> AStar {
+        AStar::new(
+            DistanceCalculator::Manhattan,
+            Budget::default(),
+            heuristic_weight,
+            tie_breaking,
+        )
+    }
+
+    fn choose_start_and_goal<N, E, G>(random: &mut Random, graph: &G) -> (usize, usize)
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let valid: Vec<usize> = (0..graph.get_size())
+            .filter(|index| graph.is_valid(*index))
+            .collect();
+
+        (
+            *random.choose(&valid).unwrap(),
+            *random.choose(&valid).unwrap(),
+        )
+    }
+
+    /// Relaxes all edges until nothing changes, like Bellman-Ford
+    fn find_cheapest_cost<N, E, G>(graph: &G, start: usize, goal: usize) -> Option<u32>
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let mut costs = vec![u32::MAX; graph.get_size()];
+        costs[start] = 0;
+        let mut is_changed = true;
+
+        while is_changed {
+            is_changed = false;
+
+            for index in 0..graph.get_size() {
+                if costs[index] == u32::MAX {
+                    continue;
+                }
+
+                for neighbor in graph.get_neighbors(index) {
+                    if !graph.is_valid(neighbor.index) {
+                        continue;
+                    }
+
+                    let cost = costs[index] + graph.calculate_cost(index, &neighbor);
+
+                    if cost < costs[neighbor.index] {
+                        costs[neighbor.index] = cost;
+                        is_changed = true;
+                    }
+                }
+            }
+        }
+
+        Some(costs[goal]).filter(|cost| *cost != u32::MAX)
+    }
+
+    /// Checks that the path is connected and returns its cost
+    fn get_cost<N, E, G>(graph: &G, start: usize, result: &PathfindingResult) -> Option<u32>
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        match result {
+            Path { indices, cost } => {
+                let mut previous = start;
+                let mut total = 0;
+
+                for index in indices {
+                    let neighbor = graph
+                        .get_neighbors(previous)
+                        .into_iter()
+                        .find(|neighbor| neighbor.index == *index)
+                        .expect("Path is not connected");
+                    assert!(graph.is_valid(*index));
+                    total += graph.calculate_cost(previous, &neighbor);
+                    previous = *index;
+                }
+
+                assert_eq!(total, *cost);
+                Some(*cost)
+            }
+            GoalAlreadyReached => Some(0),
+            NoPathFound => None,
+            _ => panic!("Unexpected result {:?}", result),
+        }
+    }
 }
@@ -0,0 +1,158 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Reusable storage for pathfinding, which avoids allocations for repeated searches.
+///
+/// The storage grows to the size of the largest searched graph and is never cleared.
+/// Instead each search uses a new generation to mark its own nodes.
+#[derive(Debug, Default)]
+pub struct SearchContext {
+    nodes: Vec<Node>,
+    generations: Vec<u32>,
+    /// The generation, in which each node was last visited by [`SearchContext::visit`]
+    visits: Vec<u32>,
+    generation: u32,
+    pub(crate) open_nodes: BinaryHeap<OpenNode>,
+    /// Nodes for later processing, e.g. those exceeding the maximum cost
+    pub(crate) pending_nodes: Vec<usize>,
+}
+
+impl SearchContext {
+    /// Creates a new SearchContext for graphs with `size` nodes
+    pub fn new(size: usize) -> Self {
+        let mut context = SearchContext::default();
+        context.prepare(size);
+        context
+    }
+
+    /// Prepares the context for a new search of a graph with `size` nodes
+    pub(crate) fn prepare(&mut self, size: usize) {
+        if self.nodes.len() < size {
+            self.nodes.resize(size, Node::default());
+            self.generations.resize(size, 0);
+            self.visits.resize(size, 0);
+        }
+
+        self.generation = self.generation.wrapping_add(1);
+
+        if self.generation == 0 {
+            self.generations.iter_mut().for_each(|g| *g = 0);
+            self.visits.iter_mut().for_each(|g| *g = 0);
+            self.generation = 1;
+        }
+
+        self.open_nodes.clear();
+        self.pending_nodes.clear();
+    }
+
+    /// Marks the node as visited and returns false, if it was already visited in the current search
+    pub(crate) fn visit(&mut self, index: usize) -> bool {
+        if self.visits[index] == self.generation {
+            return false;
+        }
+
+        self.visits[index] = self.generation;
+        true
+    }
+
+    /// Returns the node, if it was reached in the current search
+    pub(crate) fn get(&self, index: usize) -> Option<&Node> {
+        if self.generations[index] == self.generation {
+            Some(&self.nodes[index])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the node and initializes it, if it wasn't reached in the current search
+    pub(crate) fn get_or_insert(
+        &mut self,
+        index: usize,
+        heuristic: impl FnOnce() -> u32,
+    ) -> &mut Node {
+        if self.generations[index] != self.generation {
+            self.generations[index] = self.generation;
+            self.nodes[index] = Node::new(heuristic());
+        }
+
+        &mut self.nodes[index]
+    }
+
+    /// Backtracks the path from the end to the start node
+    pub(crate) fn create_path(&self, end: usize) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut current = end;
+
+        while let Some(previous) = self.get(current).and_then(|node| node.previous) {
+            indices.push(current);
+            current = previous;
+        }
+
+        indices.reverse();
+        indices
+    }
+}
+
+/// An entry of the open set. The lowest total cost comes first, then the lowest tie breaker.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct OpenNode {
+    pub index: usize,
+    pub total_cost: u32,
+    pub tie_breaker: u64,
+}
+
+impl OpenNode {
+    pub fn new(index: usize, total_cost: u32, tie_breaker: u64) -> Self {
+        OpenNode {
+            index,
+            total_cost,
+            tie_breaker,
+        }
+    }
+
+    pub fn start(index: usize) -> Self {
+        OpenNode::new(index, 0, 0)
+    }
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .total_cost
+            .cmp(&self.total_cost)
+            .then(other.tie_breaker.cmp(&self.tie_breaker))
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Node {
+    pub cost: u32,
+    pub heuristic: u32,
+    pub total_cost: u32,
+    pub previous: Option<usize>,
+}
+
+impl Node {
+    pub fn new(heuristic: u32) -> Self {
+        Node {
+            cost: u32::MAX,
+            heuristic,
+            total_cost: u32::MAX,
+            previous: None,
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use crate::algorithm::pathfinding::PathfindingResult::*;
+use crate::algorithm::pathfinding::{CostCalculator, PathfindingResult};
+use crate::math::graph::Graph;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+/// The cheapest way from every node of a graph to the nearest of its goals.
+///
+/// Many units can share one flow field instead of searching their own paths.
+/// It must be recreated after the graph or the goals changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlowField {
+    costs: Vec<u32>,
+    next: Vec<Option<usize>>,
+}
+
+impl FlowField {
+    /// Creates a flow field towards a single goal
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::pathfinding::flow_field::FlowField;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// // #####
+    /// // #...#
+    /// // #.#.#
+    /// // #####
+    /// let mut map = OccupancyMap::new(Size2d::new(5, 4), false);
+    /// map.add_border();
+    /// map.set_node(12, true);
+    /// let field = FlowField::new(&map, 11);
+    ///
+    /// assert_eq!(field.get_cost(8), Some(3));
+    /// assert_eq!(field.get_next(8), Some(7));
+    /// assert_eq!(field.get_next(13), Some(8));
+    /// assert_eq!(field.get_next(11), None);
+    /// assert_eq!(field.get_cost(12), None);
+    /// ```
+    pub fn new<N, E, G>(graph: &G, goal: usize) -> Self
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        FlowField::with_goals(graph, &[goal])
+    }
+
+    /// Creates a flow field, where each node leads to its cheapest goal.
+    ///
+    /// Invalid goals are ignored. The edges of the graph are assumed to go both ways.
+    pub fn with_goals<N, E, G>(graph: &G, goals: &[usize]) -> Self
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let size = graph.get_size();
+        let mut field = FlowField {
+            costs: vec![u32::MAX; size],
+            next: vec![None; size],
+        };
+        let mut open_nodes = BinaryHeap::new();
+
+        for goal in goals {
+            if *goal < size && graph.is_valid(*goal) {
+                field.costs[*goal] = 0;
+                open_nodes.push(Reverse((0, *goal)));
+            }
+        }
+
+        while let Some(Reverse((cost, index))) = open_nodes.pop() {
+            if cost > field.costs[index] {
+                continue;
+            }
+
+            for neighbor in graph.get_neighbors(index) {
+                if !graph.is_valid(neighbor.index) {
+                    continue;
+                }
+
+                // the cost of moving from the neighbor towards the goal
+                let step_cost = graph
+                    .get_neighbors(neighbor.index)
+                    .iter()
+                    .find(|back| back.index == index)
+                    .map(|back| graph.calculate_cost(neighbor.index, back));
+                let new_cost = match step_cost {
+                    Some(step_cost) => cost.saturating_add(step_cost),
+                    None => continue,
+                };
+
+                if new_cost < field.costs[neighbor.index] {
+                    field.costs[neighbor.index] = new_cost;
+                    field.next[neighbor.index] = Some(index);
+                    open_nodes.push(Reverse((new_cost, neighbor.index)));
+                }
+            }
+        }
+
+        field
+    }
+
+    /// Returns the cost of reaching the nearest goal or `None`, if no goal is reachable
+    pub fn get_cost(&self, index: usize) -> Option<u32> {
+        self.costs
+            .get(index)
+            .cloned()
+            .filter(|cost| *cost != u32::MAX)
+    }
+
+    /// Returns the next node towards the nearest goal. Goals have no next node.
+    pub fn get_next(&self, index: usize) -> Option<usize> {
+        self.next.get(index).cloned().flatten()
+    }
+
+    /// Returns the edge to the next node, which is a [`Direction2d`](crate::math::graph::map2d::Direction2d) for 2d maps
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::pathfinding::flow_field::FlowField;
+    ///# use rust_roguelike_core::math::graph::map2d::Direction2d;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = OccupancyMap::new(Size2d::new(3, 1), false);
+    /// let field = FlowField::new(&map, 2);
+    ///
+    /// assert!(matches!(field.get_direction(&map, 0), Some(Direction2d::EAST)));
+    /// assert!(field.get_direction(&map, 2).is_none());
+    /// ```
+    pub fn get_direction<N, E, G>(&self, graph: &G, index: usize) -> Option<E>
+    where
+        G: Graph<N, E>,
+    {
+        let next = self.get_next(index)?;
+
+        graph
+            .get_neighbors(index)
+            .into_iter()
+            .find(|neighbor| neighbor.index == next)
+            .map(|neighbor| neighbor.edge)
+    }
+
+    /// Follows the flow field from the start node to the nearest goal.
+    ///
+    /// Invalid start nodes can't reach any goal either.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::pathfinding::flow_field::FlowField;
+    ///# use rust_roguelike_core::algorithm::pathfinding::PathfindingResult;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut map = OccupancyMap::new(Size2d::new(6, 1), false);
+    /// map.set_node(3, true);
+    /// let field = FlowField::new(&map, 0);
+    ///
+    /// assert_eq!(field.follow(2), PathfindingResult::Path { indices: vec![1, 0], cost: 2 });
+    /// assert_eq!(field.follow(0), PathfindingResult::GoalAlreadyReached);
+    /// assert_eq!(field.follow(5), PathfindingResult::NoPathFound);
+    /// ```
+    pub fn follow(&self, start: usize) -> PathfindingResult {
+        let cost = match self.get_cost(start) {
+            Some(0) => return GoalAlreadyReached,
+            Some(cost) => cost,
+            None => return NoPathFound,
+        };
+
+        let mut indices = Vec::new();
+        let mut current = start;
+
+        while let Some(next) = self.get_next(current) {
+            indices.push(next);
+            current = next;
+        }
+
+        Path { indices, cost }
+    }
+
+    /// Moves each unit one step towards its nearest goal.
+    ///
+    /// A unit waits, if its next node is already taken by another unit.
+    /// Earlier units move first. Each unit must start on its own node.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::pathfinding::flow_field::FlowField;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = OccupancyMap::new(Size2d::new(5, 1), false);
+    /// let field = FlowField::new(&map, 0);
+    /// let mut units = vec![1, 3, 2];
+    ///
+    /// field.move_units(&mut units);
+    ///
+    /// assert_eq!(units, vec![0, 3, 1]);
+    /// ```
+    pub fn move_units(&self, units: &mut [usize]) {
+        let mut occupied: HashSet<usize> = units.iter().cloned().collect();
+
+        for unit in units.iter_mut() {
+            if let Some(next) = self.get_next(*unit) {
+                if occupied.insert(next) {
+                    occupied.remove(unit);
+                    *unit = next;
+                }
+            }
+        }
+    }
+
+    /// Merges 2 flow fields, so each node leads to the cheaper goal of both.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flow fields were created for graphs of different sizes.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::pathfinding::flow_field::FlowField;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = OccupancyMap::new(Size2d::new(7, 1), false);
+    /// let merged = FlowField::new(&map, 0).merge(&FlowField::new(&map, 6));
+    ///
+    /// assert_eq!(merged, FlowField::with_goals(&map, &[0, 6]));
+    /// assert_eq!(merged.get_next(2), Some(1));
+    /// assert_eq!(merged.get_next(4), Some(5));
+    /// ```
+    pub fn merge(&self, other: &FlowField) -> FlowField {
+        assert_eq!(
+            self.costs.len(),
+            other.costs.len(),
+            "Can't merge flow fields of different graphs"
+        );
+
+        let (costs, next) = self
+            .costs
+            .iter()
+            .zip(self.next.iter())
+            .zip(other.costs.iter().zip(other.next.iter()))
+            .map(|((cost, next), (other_cost, other_next))| {
+                if other_cost < cost {
+                    (*other_cost, *other_next)
+                } else {
+                    (*cost, *next)
+                }
+            })
+            .unzip();
+
+        FlowField { costs, next }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::graph::cost::CostMap;
+    use crate::math::graph::occupancy::OccupancyMap;
+    use crate::math::size2d::Size2d;
+
+    #[test]
+    fn test_costs_in_open_room() {
+        let size = Size2d::new(7, 6);
+        let mut map = OccupancyMap::new(size, false);
+        map.add_border();
+        let field = FlowField::new(&map, size.to_index(1, 1));
+
+        for y in 1..5 {
+            for x in 1..6 {
+                assert_eq!(field.get_cost(size.to_index(x, y)), Some(x - 1 + y - 1));
+            }
+        }
+
+        assert_eq!(field.get_cost(0), None);
+        assert_eq!(field.get_next(0), None);
+    }
+
+    #[test]
+    fn test_avoid_swamp() {
+        // .....
+        // .~~~.
+        // G~~~S
+        let mut map = CostMap::new(Size2d::new(5, 3), 1);
+        for index in &[1, 2, 3, 6, 7, 8] {
+            map.set_cost(*index, 10);
+        }
+        let field = FlowField::new(&map, 0);
+
+        assert_eq!(
+            field.follow(4),
+            Path {
+                indices: vec![9, 14, 13, 12, 11, 10, 5, 0],
+                cost: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_goal() {
+        let mut map = OccupancyMap::new(Size2d::new(3, 3), false);
+        map.add_border();
+        let field = FlowField::with_goals(&map, &[0, 9]);
+
+        assert_eq!(field.follow(4), NoPathFound);
+    }
+
+    #[test]
+    fn test_merge_prefers_cheaper_goal() {
+        let mut map = CostMap::new(Size2d::new(5, 1), 1);
+        map.set_cost(1, 5);
+        let left = FlowField::new(&map, 0);
+        let right = FlowField::new(&map, 4);
+        let merged = left.merge(&right);
+
+        assert_eq!(merged.get_next(2), Some(3));
+        assert_eq!(merged.get_cost(1), Some(1));
+        assert_eq!(merged, FlowField::with_goals(&map, &[0, 4]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_different_sizes() {
+        let small = OccupancyMap::new(Size2d::new(3, 1), false);
+        let large = OccupancyMap::new(Size2d::new(5, 1), false);
+
+        FlowField::new(&small, 0).merge(&FlowField::new(&large, 0));
+    }
+
+    #[test]
+    fn test_units_wait_in_line() {
+        let map = OccupancyMap::new(Size2d::new(6, 1), false);
+        let field = FlowField::new(&map, 0);
+        let mut units = vec![2, 1, 3, 5];
+
+        field.move_units(&mut units);
+
+        assert_eq!(units, vec![2, 0, 3, 4]);
+
+        field.move_units(&mut units);
+
+        assert_eq!(units, vec![1, 0, 2, 3]);
+    }
+
+    #[test]
+    fn test_units_reach_goal() {
+        let size = Size2d::new(8, 6);
+        let mut map = OccupancyMap::new(size, false);
+        map.add_border();
+        let goal = size.to_index(1, 1);
+        let field = FlowField::new(&map, goal);
+        let mut units = vec![
+            size.to_index(6, 4),
+            size.to_index(6, 1),
+            size.to_index(1, 4),
+        ];
+
+        for _ in 0..10 {
+            field.move_units(&mut units);
+        }
+
+        assert!(units.contains(&goal));
+        assert!(units.iter().all(|unit| field.get_cost(*unit).unwrap() <= 2));
+        assert!(units[1..].iter().all(|unit| *unit != units[0]));
+        assert_ne!(units[1], units[2]);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::algorithm::pathfinding::CostCalculator;
+use crate::math::distance::DistanceCalculator;
+use crate::math::graph::Graph;
+
+/// Defines when a search reached its goal
+#[derive(Clone, Copy)]
+pub enum Goal<'a> {
+    /// A single node
+    Node(usize),
+    /// The nearest of several nodes
+    AnyOf(&'a [usize]),
+    /// Any node within a distance of the target, e.g. for ranged attackers
+    WithinRange { target: usize, range: u32 },
+    /// Any node matching the predicate. Without a heuristic the search is slower.
+    Predicate(&'a dyn Fn(usize) -> bool),
+}
+
+impl<'a> Goal<'a> {
+    /// Is the node a goal?
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::pathfinding::goal::Goal;
+    ///# use rust_roguelike_core::math::distance::DistanceCalculator::Chebyshev;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = OccupancyMap::new(Size2d::new(5, 5), false);
+    /// let goal = Goal::WithinRange { target: 12, range: 1 };
+    ///
+    /// assert!(goal.is_reached(&map, Chebyshev, 6));
+    /// assert!(!goal.is_reached(&map, Chebyshev, 5));
+    /// ```
+    pub fn is_reached<N, E, G>(
+        &self,
+        graph: &G,
+        calculator: DistanceCalculator,
+        index: usize,
+    ) -> bool
+    where
+        G: Graph<N, E>,
+    {
+        match self {
+            Goal::Node(goal) => *goal == index,
+            Goal::AnyOf(goals) => goals.contains(&index),
+            Goal::WithinRange { target, range } => {
+                graph.get_distance(calculator, index, *target) <= *range
+            }
+            Goal::Predicate(predicate) => predicate(index),
+        }
+    }
+
+    /// Returns a lower bound of the distance between the node and the nearest goal
+    pub(crate) fn estimate<N, E, G>(
+        &self,
+        graph: &G,
+        calculator: DistanceCalculator,
+        index: usize,
+    ) -> u32
+    where
+        G: Graph<N, E>,
+    {
+        match self {
+            Goal::Node(goal) => graph.get_distance(calculator, index, *goal),
+            Goal::AnyOf(goals) => goals
+                .iter()
+                .map(|goal| graph.get_distance(calculator, index, *goal))
+                .min()
+                .unwrap_or(0),
+            Goal::WithinRange { target, range } => graph
+                .get_distance(calculator, index, *target)
+                .saturating_sub(*range),
+            Goal::Predicate(_) => 0,
+        }
+    }
+
+    /// Can any node reach the goal?
+    pub(crate) fn is_valid<N, E, G>(&self, graph: &G) -> bool
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let is_valid = |goal: usize| goal < graph.get_size() && graph.is_valid(goal);
+
+        match self {
+            Goal::Node(goal) => is_valid(*goal),
+            Goal::AnyOf(goals) => goals.iter().any(|goal| is_valid(*goal)),
+            Goal::WithinRange { .. } | Goal::Predicate(_) => true,
+        }
+    }
+}
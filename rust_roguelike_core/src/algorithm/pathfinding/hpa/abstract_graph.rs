@@ -0,0 +1,215 @@
+use crate::algorithm::pathfinding::CostCalculator;
+use crate::math::distance::DistanceCalculator;
+use crate::math::graph::{Graph, Neighbor};
+use crate::math::size2d::Size2d;
+use std::collections::HashMap;
+
+/// A node of the [`AbstractGraph`], which is the entrance tile of a cluster
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbstractNode {
+    pub tile: usize,
+    pub cluster: usize,
+}
+
+/// An edge of the [`AbstractGraph`] with the cost of the path between its nodes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbstractEdge {
+    /// Between adjacent tiles of neighboring clusters
+    Inter(u32),
+    /// Between entrances of the same cluster
+    Intra(u32),
+}
+
+impl AbstractEdge {
+    pub fn get_cost(&self) -> u32 {
+        match self {
+            AbstractEdge::Inter(cost) => *cost,
+            AbstractEdge::Intra(cost) => *cost,
+        }
+    }
+}
+
+/// The graph of all cluster entrances of a map.
+///
+/// The indices of removed nodes stay invalid until they are reused by new nodes.
+#[derive(Clone, Debug, Default)]
+pub struct AbstractGraph {
+    nodes: Vec<Option<AbstractNode>>,
+    neighbors: Vec<Vec<Neighbor<AbstractEdge>>>,
+    free_indices: Vec<usize>,
+    tiles: HashMap<usize, usize>,
+    size: Size2d,
+}
+
+impl AbstractGraph {
+    /// Creates a new AbstractGraph without nodes for a map of a specific size
+    pub fn new(size: Size2d) -> Self {
+        AbstractGraph {
+            size,
+            ..AbstractGraph::default()
+        }
+    }
+
+    /// Returns the index of the node at a tile
+    pub fn get_index(&self, tile: usize) -> Option<usize> {
+        self.tiles.get(&tile).cloned()
+    }
+
+    /// Returns the number of valid nodes
+    pub fn get_node_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Adds a node, if its tile has none yet. Returns the index of the tile's node.
+    pub(crate) fn add_node(&mut self, node: AbstractNode) -> usize {
+        if let Some(index) = self.get_index(node.tile) {
+            return index;
+        }
+
+        let index = match self.free_indices.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.neighbors.push(Vec::new());
+                self.nodes.len() - 1
+            }
+        };
+
+        self.tiles.insert(node.tile, index);
+        index
+    }
+
+    /// Removes a node and all its edges
+    pub(crate) fn remove_node(&mut self, index: usize) {
+        if let Some(node) = self.nodes[index].take() {
+            self.tiles.remove(&node.tile);
+            self.free_indices.push(index);
+
+            for neighbor in std::mem::take(&mut self.neighbors[index]) {
+                self.neighbors[neighbor.index].retain(|n| n.index != index);
+            }
+        }
+    }
+
+    /// Adds an edge from one node to another or replaces the existing one
+    pub(crate) fn add_edge(&mut self, from: usize, to: usize, edge: AbstractEdge) {
+        let neighbors = &mut self.neighbors[from];
+
+        match neighbors.iter_mut().find(|neighbor| neighbor.index == to) {
+            Some(neighbor) => neighbor.edge = edge,
+            None => neighbors.push(Neighbor { index: to, edge }),
+        }
+    }
+
+    /// Removes all edges starting at a node inside its cluster
+    pub(crate) fn remove_intra_edges(&mut self, index: usize) {
+        self.neighbors[index].retain(|n| !matches!(n.edge, AbstractEdge::Intra(_)));
+    }
+
+    pub(crate) fn get_tile(&self, index: usize) -> Option<usize> {
+        self.get_node(index).map(|node| node.tile)
+    }
+}
+
+impl Graph<AbstractNode, AbstractEdge> for AbstractGraph {
+    fn get_size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn get_node(&self, index: usize) -> Option<&AbstractNode> {
+        self.nodes.get(index).and_then(Option::as_ref)
+    }
+
+    fn get_neighbors(&self, index: usize) -> Vec<Neighbor<AbstractEdge>> {
+        self.neighbors.get(index).cloned().unwrap_or_default()
+    }
+
+    fn get_distance(&self, calculator: DistanceCalculator, from: usize, to: usize) -> u32 {
+        match (self.get_tile(from), self.get_tile(to)) {
+            (Some(from), Some(to)) => get_tile_distance(self.size, calculator, from, to),
+            _ => 0,
+        }
+    }
+}
+
+impl CostCalculator<AbstractEdge> for AbstractGraph {
+    fn is_valid(&self, index: usize) -> bool {
+        self.get_node(index).is_some()
+    }
+
+    fn calculate_cost(&self, _index: usize, neighbor: &Neighbor<AbstractEdge>) -> u32 {
+        neighbor.edge.get_cost()
+    }
+}
+
+pub(crate) fn get_tile_distance(
+    size: Size2d,
+    calculator: DistanceCalculator,
+    from: usize,
+    to: usize,
+) -> u32 {
+    let [from_x, from_y] = size.to_point(from);
+    let [to_x, to_y] = size.to_point(to);
+    calculator.calculate_2d(from_x as i32, from_y as i32, to_x as i32, to_y as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_remove_nodes() {
+        let mut graph = AbstractGraph::new(Size2d::new(4, 4));
+        let a = graph.add_node(AbstractNode {
+            tile: 1,
+            cluster: 0,
+        });
+        let b = graph.add_node(AbstractNode {
+            tile: 2,
+            cluster: 1,
+        });
+        graph.add_edge(a, b, AbstractEdge::Inter(1));
+        graph.add_edge(b, a, AbstractEdge::Inter(1));
+
+        assert_eq!(graph.get_index(2), Some(b));
+        assert_eq!(graph.get_neighbors(a).len(), 1);
+
+        graph.remove_node(b);
+
+        assert!(!graph.is_valid(b));
+        assert_eq!(graph.get_index(2), None);
+        assert!(graph.get_neighbors(a).is_empty());
+        assert_eq!(graph.get_node_count(), 1);
+
+        let c = graph.add_node(AbstractNode {
+            tile: 3,
+            cluster: 1,
+        });
+
+        assert_eq!(c, b);
+        assert_eq!(graph.get_size(), 2);
+    }
+
+    #[test]
+    fn test_replace_edge() {
+        let mut graph = AbstractGraph::new(Size2d::new(4, 4));
+        let a = graph.add_node(AbstractNode {
+            tile: 1,
+            cluster: 0,
+        });
+        let b = graph.add_node(AbstractNode {
+            tile: 2,
+            cluster: 0,
+        });
+        graph.add_edge(a, b, AbstractEdge::Intra(3));
+        graph.add_edge(a, b, AbstractEdge::Intra(5));
+
+        let neighbors = graph.get_neighbors(a);
+
+        assert_eq!(neighbors.len(), 1);
+        assert_eq!(graph.calculate_cost(a, &neighbors[0]), 5);
+    }
+}
@@ -0,0 +1,795 @@
+pub mod abstract_graph;
+
+use crate::algorithm::pathfinding::a_star::AStar;
+use crate::algorithm::pathfinding::hpa::abstract_graph::*;
+use crate::algorithm::pathfinding::PathfindingResult::*;
+use crate::algorithm::pathfinding::{CostCalculator, PathfindingAlgorithm, PathfindingResult};
+use crate::math::distance::DistanceCalculator;
+use crate::math::graph::map2d::Map2d;
+use crate::math::graph::{Graph, Neighbor};
+use crate::math::rectangle::Rectangle;
+use crate::math::size2d::Size2d;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::Debug;
+
+/// Entrances up to this width get a single transition in their middle, wider ones one at each end
+const MAX_NARROW_ENTRANCE: u32 = 6;
+
+/// Hierarchical pathfinding for large 2d maps
+///
+/// The map is partitioned into square clusters. The [`AbstractGraph`] connects the entrances
+/// between neighboring clusters with the cheapest paths inside each cluster.
+/// A query searches the small abstract graph first and then refines the result tile by tile.
+/// The paths are close to optimal, but not guaranteed to be the shortest ones.
+///
+/// The hierarchy must be updated after a tile of its map changed.
+///
+/// See [Botea et al.](https://webdocs.cs.ualberta.ca/~mmueller/ps/hpastar.pdf)
+#[derive(Clone, Debug)]
+pub struct HierarchicalAStar {
+    size: Size2d,
+    cluster_size: u32,
+    clusters: Size2d,
+    transitions: HashMap<(usize, usize), Vec<(usize, usize)>>,
+    entrances: Vec<Vec<usize>>,
+    graph: AbstractGraph,
+}
+
+impl<N, E> PathfindingAlgorithm<N, E> for HierarchicalAStar {
+    /// Finds a short path from the start node to the goal node of the map used by the hierarchy.
+    ///
+    /// Returns [`PathfindingResult::NotSearched`], if the graph doesn't match the size of the map.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::hpa::HierarchicalAStar;
+    ///# use rust_roguelike_core::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+    /// let mut map = OccupancyMap::new(Size2d::new(8, 3), false);
+    /// map.add_border();
+    /// let algorithm = HierarchicalAStar::new(&map, 4);
+    ///
+    /// assert_eq!(algorithm.find(&map, 9, 14),
+    ///            PathfindingResult::Path {
+    ///              indices: vec![10, 11, 12, 13, 14],
+    ///              cost: 5,
+    ///            });
+    /// ```
+    fn find<G>(&self, graph: &G, start: usize, goal: usize) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+        E: Debug,
+    {
+        let tiles = self.size.get_tiles();
+
+        if graph.get_size() != tiles {
+            return NotSearched;
+        } else if start == goal {
+            return GoalAlreadyReached;
+        } else if start >= tiles || !graph.is_valid(start) {
+            return InvalidStart;
+        } else if goal >= tiles || !graph.is_valid(goal) {
+            return InvalidGoal;
+        }
+
+        let query = QueryGraph::new(self, graph, start, goal);
+
+        match AStar::default().find(&query, query.get_start(), query.get_goal()) {
+            Path { indices, .. } => {
+                let tiles: Vec<usize> = indices
+                    .into_iter()
+                    .filter_map(|index| query.get_tile(index))
+                    .collect();
+                self.refine(graph, start, &tiles)
+            }
+            _ => NoPathFound,
+        }
+    }
+}
+
+impl HierarchicalAStar {
+    /// Creates the hierarchy of a map with clusters of `cluster_size` x `cluster_size` tiles
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::hpa::HierarchicalAStar;
+    /// let map = OccupancyMap::new(Size2d::new(10, 4), false);
+    /// let hierarchy = HierarchicalAStar::new(&map, 4);
+    ///
+    /// assert_eq!(hierarchy.get_cluster_count(), 3);
+    /// assert_eq!(hierarchy.get_cluster(9), 2);
+    /// assert_eq!(hierarchy.get_cluster_area(2).size(), Size2d::new(2, 4));
+    /// assert_eq!(hierarchy.get_abstract_graph().get_node_count(), 4);
+    /// ```
+    pub fn new<N, E, G>(graph: &G, cluster_size: u32) -> Self
+    where
+        G: Map2d<N, E> + CostCalculator<E>,
+    {
+        let size = graph.get_size_2d();
+        let cluster_size = cluster_size.max(1);
+        let clusters = Size2d::new(
+            size.width().div_ceil(cluster_size),
+            size.height().div_ceil(cluster_size),
+        );
+        let mut hierarchy = HierarchicalAStar {
+            size,
+            cluster_size,
+            clusters,
+            transitions: HashMap::new(),
+            entrances: vec![Vec::new(); clusters.get_tiles()],
+            graph: AbstractGraph::new(size),
+        };
+
+        let all_clusters: Vec<usize> = (0..clusters.get_tiles()).collect();
+
+        for cluster in &all_clusters {
+            for (first, second) in hierarchy.get_borders(*cluster) {
+                if first == *cluster {
+                    hierarchy.detect_transitions(graph, first, second);
+                }
+            }
+        }
+
+        hierarchy.rebuild_clusters(graph, &all_clusters);
+        hierarchy
+    }
+
+    /// Returns the number of clusters
+    pub fn get_cluster_count(&self) -> usize {
+        self.clusters.get_tiles()
+    }
+
+    /// Returns the cluster of a tile
+    pub fn get_cluster(&self, tile: usize) -> usize {
+        let [x, y] = self.size.to_point(tile);
+        self.clusters
+            .to_index(x / self.cluster_size, y / self.cluster_size)
+    }
+
+    /// Returns the tiles of a cluster. Clusters at the edge of the map can be smaller.
+    pub fn get_cluster_area(&self, cluster: usize) -> Rectangle {
+        let [cluster_x, cluster_y] = self.clusters.to_point(cluster);
+        let x = cluster_x * self.cluster_size;
+        let y = cluster_y * self.cluster_size;
+        let width = self.cluster_size.min(self.size.width() - x);
+        let height = self.cluster_size.min(self.size.height() - y);
+
+        Rectangle::new(x, y, Size2d::new(width, height))
+    }
+
+    pub fn get_abstract_graph(&self) -> &AbstractGraph {
+        &self.graph
+    }
+
+    /// Rebuilds the clusters affected by a changed tile
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::hpa::HierarchicalAStar;
+    ///# use rust_roguelike_core::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+    /// let mut map = OccupancyMap::new(Size2d::new(8, 3), false);
+    /// map.add_border();
+    /// let mut hierarchy = HierarchicalAStar::new(&map, 4);
+    ///
+    /// map.set_node(11, true);
+    /// hierarchy.update(&map, 11);
+    ///
+    /// assert_eq!(hierarchy.find(&map, 9, 14), PathfindingResult::NoPathFound);
+    /// ```
+    pub fn update<N, E, G>(&mut self, graph: &G, tile: usize)
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let cluster = self.get_cluster(tile);
+        let [x, y] = self.size.to_point(tile);
+        let area = self.get_cluster_area(cluster);
+        let mut affected = vec![cluster];
+
+        for (first, second) in self.get_borders(cluster) {
+            let other = if first == cluster { second } else { first };
+            let other_area = self.get_cluster_area(other);
+            let is_on_border = if other_area.x() == area.end_x() {
+                x + 1 == area.end_x()
+            } else if other_area.end_x() == area.x() {
+                x == area.x()
+            } else if other_area.y() == area.end_y() {
+                y + 1 == area.end_y()
+            } else {
+                y == area.y()
+            };
+
+            if is_on_border {
+                self.detect_transitions(graph, first, second);
+                affected.push(other);
+            }
+        }
+
+        self.rebuild_clusters(graph, &affected);
+    }
+
+    /// Returns the borders of a cluster as pairs of clusters, where the first is left or above
+    fn get_borders(&self, cluster: usize) -> Vec<(usize, usize)> {
+        let [x, y] = self.clusters.to_point(cluster);
+        let width = self.clusters.width() as usize;
+        let mut borders = Vec::new();
+
+        if x > 0 {
+            borders.push((cluster - 1, cluster));
+        }
+        if x + 1 < self.clusters.width() {
+            borders.push((cluster, cluster + 1));
+        }
+        if y > 0 {
+            borders.push((cluster - width, cluster));
+        }
+        if y + 1 < self.clusters.height() {
+            borders.push((cluster, cluster + width));
+        }
+
+        borders
+    }
+
+    /// Finds the pairs of adjacent tiles, which connect 2 neighboring clusters
+    fn detect_transitions<N, E, G>(&mut self, graph: &G, first: usize, second: usize)
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let area = self.get_cluster_area(first);
+        let size = self.size;
+        let pairs: Vec<(usize, usize)> = if second == first + 1 {
+            (area.y()..area.end_y())
+                .map(|y| {
+                    (
+                        size.to_index(area.end_x() - 1, y),
+                        size.to_index(area.end_x(), y),
+                    )
+                })
+                .collect()
+        } else {
+            (area.x()..area.end_x())
+                .map(|x| {
+                    (
+                        size.to_index(x, area.end_y() - 1),
+                        size.to_index(x, area.end_y()),
+                    )
+                })
+                .collect()
+        };
+
+        let mut transitions = Vec::new();
+        let mut entrance = Vec::new();
+
+        for pair in pairs {
+            if graph.is_valid(pair.0) && graph.is_valid(pair.1) {
+                entrance.push(pair);
+            } else {
+                add_transitions(&mut transitions, &entrance);
+                entrance.clear();
+            }
+        }
+
+        add_transitions(&mut transitions, &entrance);
+
+        self.transitions.insert((first, second), transitions);
+    }
+
+    /// Replaces the abstract nodes & edges of the clusters
+    fn rebuild_clusters<N, E, G>(&mut self, graph: &G, clusters: &[usize])
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        for cluster in clusters {
+            for index in std::mem::take(&mut self.entrances[*cluster]) {
+                self.graph.remove_node(index);
+            }
+        }
+
+        for cluster in clusters {
+            for border in self.get_borders(*cluster) {
+                for (first, second) in self.transitions[&border].clone() {
+                    let tile = if self.get_cluster(first) == *cluster {
+                        first
+                    } else {
+                        second
+                    };
+                    let index = self.graph.add_node(AbstractNode {
+                        tile,
+                        cluster: *cluster,
+                    });
+
+                    if !self.entrances[*cluster].contains(&index) {
+                        self.entrances[*cluster].push(index);
+                    }
+                }
+            }
+        }
+
+        for cluster in clusters {
+            for border in self.get_borders(*cluster) {
+                for (first, second) in &self.transitions[&border] {
+                    if let (Some(a), Some(b)) =
+                        (self.graph.get_index(*first), self.graph.get_index(*second))
+                    {
+                        let cost_ab = get_step_cost(graph, *first, *second);
+                        let cost_ba = get_step_cost(graph, *second, *first);
+                        self.graph.add_edge(a, b, AbstractEdge::Inter(cost_ab));
+                        self.graph.add_edge(b, a, AbstractEdge::Inter(cost_ba));
+                    }
+                }
+            }
+
+            let area = self.get_cluster_area(*cluster);
+            let entrances = self.entrances[*cluster].clone();
+
+            for from in &entrances {
+                self.graph.remove_intra_edges(*from);
+                let from_tile = self.graph.get_tile(*from).unwrap();
+                let paths = ClusterPaths::new(graph, self.size, area, from_tile, None);
+
+                for to in &entrances {
+                    let to_tile = self.graph.get_tile(*to).unwrap();
+
+                    if let Some(cost) = paths.get_cost(to_tile).filter(|_| from != to) {
+                        self.graph.add_edge(*from, *to, AbstractEdge::Intra(cost));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Converts the tiles of the abstract path into a path of adjacent tiles
+    fn refine<N, E, G>(&self, graph: &G, start: usize, tiles: &[usize]) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let mut indices = Vec::new();
+        let mut cost = 0;
+        let mut current = start;
+
+        for tile in tiles {
+            if *tile == current {
+                continue;
+            }
+
+            let cluster = self.get_cluster(current);
+
+            if cluster != self.get_cluster(*tile) {
+                cost += get_step_cost(graph, current, *tile);
+                indices.push(*tile);
+            } else {
+                let area = self.get_cluster_area(cluster);
+                let paths = ClusterPaths::new(graph, self.size, area, current, Some(*tile));
+                cost += paths.get_cost(*tile).unwrap_or_default();
+                indices.extend(paths.get_path(*tile));
+            }
+
+            current = *tile;
+        }
+
+        Path { indices, cost }
+    }
+}
+
+/// Adds the transitions of an entrance, which is a maximal line of free tile pairs
+fn add_transitions(transitions: &mut Vec<(usize, usize)>, entrance: &[(usize, usize)]) {
+    if entrance.len() as u32 > MAX_NARROW_ENTRANCE {
+        transitions.push(entrance[0]);
+        transitions.push(entrance[entrance.len() - 1]);
+    } else if !entrance.is_empty() {
+        transitions.push(entrance[entrance.len() / 2]);
+    }
+}
+
+fn get_step_cost<N, E, G>(graph: &G, from: usize, to: usize) -> u32
+where
+    G: Graph<N, E> + CostCalculator<E>,
+{
+    graph
+        .get_neighbors(from)
+        .iter()
+        .find(|neighbor| neighbor.index == to)
+        .map(|neighbor| graph.calculate_cost(from, neighbor))
+        .unwrap_or(u32::MAX)
+}
+
+/// The cheapest paths from one tile to the other tiles of a cluster, which never leave it
+struct ClusterPaths {
+    size: Size2d,
+    area: Rectangle,
+    costs: Vec<u32>,
+    previous: Vec<Option<usize>>,
+}
+
+impl ClusterPaths {
+    /// Runs Dijkstra's algorithm, which stops early after reaching the target
+    fn new<N, E, G>(
+        graph: &G,
+        size: Size2d,
+        area: Rectangle,
+        start: usize,
+        target: Option<usize>,
+    ) -> Self
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let tiles = area.size().get_tiles();
+        let mut paths = ClusterPaths {
+            size,
+            area,
+            costs: vec![u32::MAX; tiles],
+            previous: vec![None; tiles],
+        };
+        let mut open_nodes = BinaryHeap::new();
+        let local_start = paths.to_local(start);
+        paths.costs[local_start] = 0;
+        open_nodes.push(Reverse((0, start)));
+
+        while let Some(Reverse((cost, tile))) = open_nodes.pop() {
+            if cost > paths.costs[paths.to_local(tile)] {
+                continue;
+            } else if Some(tile) == target {
+                break;
+            }
+
+            for neighbor in graph.get_neighbors(tile) {
+                let [x, y] = size.to_point(neighbor.index);
+
+                if !area.contains(x, y) || !graph.is_valid(neighbor.index) {
+                    continue;
+                }
+
+                let local = paths.to_local(neighbor.index);
+                let new_cost = cost.saturating_add(graph.calculate_cost(tile, &neighbor));
+
+                if new_cost < paths.costs[local] {
+                    paths.costs[local] = new_cost;
+                    paths.previous[local] = Some(tile);
+                    open_nodes.push(Reverse((new_cost, neighbor.index)));
+                }
+            }
+        }
+
+        paths
+    }
+
+    fn to_local(&self, tile: usize) -> usize {
+        let [x, y] = self.size.to_point(tile);
+        self.area
+            .size()
+            .to_index(x - self.area.x(), y - self.area.y())
+    }
+
+    fn get_cost(&self, tile: usize) -> Option<u32> {
+        Some(self.costs[self.to_local(tile)]).filter(|cost| *cost != u32::MAX)
+    }
+
+    /// Returns the path to the tile without the start tile
+    fn get_path(&self, tile: usize) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut current = tile;
+
+        while let Some(previous) = self.previous[self.to_local(current)] {
+            indices.push(current);
+            current = previous;
+        }
+
+        indices.reverse();
+        indices
+    }
+}
+
+/// The abstract graph with the start & goal of a query as 2 additional nodes
+struct QueryGraph<'a> {
+    graph: &'a AbstractGraph,
+    size: Size2d,
+    nodes: [AbstractNode; 2],
+    start_neighbors: Vec<Neighbor<AbstractEdge>>,
+    goal_costs: HashMap<usize, u32>,
+}
+
+impl<'a> QueryGraph<'a> {
+    fn new<N, E, G>(hierarchy: &'a HierarchicalAStar, graph: &G, start: usize, goal: usize) -> Self
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let abstract_graph = &hierarchy.graph;
+        let start_cluster = hierarchy.get_cluster(start);
+        let goal_cluster = hierarchy.get_cluster(goal);
+        let goal_index = abstract_graph.get_size() + 1;
+
+        let area = hierarchy.get_cluster_area(start_cluster);
+        let paths = ClusterPaths::new(graph, hierarchy.size, area, start, None);
+        let mut start_neighbors: Vec<Neighbor<AbstractEdge>> = hierarchy.entrances[start_cluster]
+            .iter()
+            .filter_map(|index| {
+                let tile = abstract_graph.get_tile(*index)?;
+                paths.get_cost(tile).map(|cost| Neighbor {
+                    index: *index,
+                    edge: AbstractEdge::Intra(cost),
+                })
+            })
+            .collect();
+
+        if start_cluster == goal_cluster {
+            if let Some(cost) = paths.get_cost(goal) {
+                start_neighbors.push(Neighbor {
+                    index: goal_index,
+                    edge: AbstractEdge::Intra(cost),
+                });
+            }
+        }
+
+        let area = hierarchy.get_cluster_area(goal_cluster);
+        let goal_costs = hierarchy.entrances[goal_cluster]
+            .iter()
+            .filter_map(|index| {
+                let tile = abstract_graph.get_tile(*index)?;
+                let paths = ClusterPaths::new(graph, hierarchy.size, area, tile, Some(goal));
+                paths.get_cost(goal).map(|cost| (*index, cost))
+            })
+            .collect();
+
+        QueryGraph {
+            graph: abstract_graph,
+            size: hierarchy.size,
+            nodes: [
+                AbstractNode {
+                    tile: start,
+                    cluster: start_cluster,
+                },
+                AbstractNode {
+                    tile: goal,
+                    cluster: goal_cluster,
+                },
+            ],
+            start_neighbors,
+            goal_costs,
+        }
+    }
+
+    fn get_start(&self) -> usize {
+        self.graph.get_size()
+    }
+
+    fn get_goal(&self) -> usize {
+        self.graph.get_size() + 1
+    }
+
+    fn get_tile(&self, index: usize) -> Option<usize> {
+        self.get_node(index).map(|node| node.tile)
+    }
+}
+
+impl<'a> Graph<AbstractNode, AbstractEdge> for QueryGraph<'a> {
+    fn get_size(&self) -> usize {
+        self.graph.get_size() + 2
+    }
+
+    fn get_node(&self, index: usize) -> Option<&AbstractNode> {
+        match index.checked_sub(self.graph.get_size()) {
+            Some(extra) => self.nodes.get(extra),
+            None => self.graph.get_node(index),
+        }
+    }
+
+    fn get_neighbors(&self, index: usize) -> Vec<Neighbor<AbstractEdge>> {
+        if index == self.get_start() {
+            return self.start_neighbors.clone();
+        }
+
+        let mut neighbors = self.graph.get_neighbors(index);
+
+        if let Some(cost) = self.goal_costs.get(&index) {
+            neighbors.push(Neighbor {
+                index: self.get_goal(),
+                edge: AbstractEdge::Intra(*cost),
+            });
+        }
+
+        neighbors
+    }
+
+    fn get_distance(&self, calculator: DistanceCalculator, from: usize, to: usize) -> u32 {
+        match (self.get_tile(from), self.get_tile(to)) {
+            (Some(from), Some(to)) => get_tile_distance(self.size, calculator, from, to),
+            _ => 0,
+        }
+    }
+}
+
+impl<'a> CostCalculator<AbstractEdge> for QueryGraph<'a> {
+    fn is_valid(&self, index: usize) -> bool {
+        self.get_node(index).is_some()
+    }
+
+    fn calculate_cost(&self, _index: usize, neighbor: &Neighbor<AbstractEdge>) -> u32 {
+        neighbor.edge.get_cost()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::generation::cave::CaveGenerator;
+    use crate::math::graph::cost::CostMap;
+    use crate::math::graph::occupancy::OccupancyMap;
+
+    fn assert_valid_path<N, E, G>(graph: &G, start: usize, result: &PathfindingResult)
+    where
+        G: Map2d<N, E> + CostCalculator<E>,
+    {
+        if let Path { indices, cost } = result {
+            let mut previous = start;
+            let mut total = 0;
+
+            for index in indices {
+                assert!(graph.is_valid(*index));
+                let step_cost = get_step_cost(graph, previous, *index);
+                assert_ne!(
+                    step_cost,
+                    u32::MAX,
+                    "{} is no neighbor of {}",
+                    index,
+                    previous
+                );
+                total += step_cost;
+                previous = *index;
+            }
+
+            assert_eq!(total, *cost);
+        } else {
+            panic!("No path: {:?}", result);
+        }
+    }
+
+    /// Returns all edges of the abstract graph by the tiles of their nodes
+    fn get_edges(hierarchy: &HierarchicalAStar) -> Vec<(usize, usize, AbstractEdge)> {
+        let graph = hierarchy.get_abstract_graph();
+        let mut edges: Vec<(usize, usize, AbstractEdge)> = (0..graph.get_size())
+            .flat_map(|index| {
+                graph.get_neighbors(index).into_iter().map(move |neighbor| {
+                    (
+                        graph.get_tile(index).unwrap(),
+                        graph.get_tile(neighbor.index).unwrap(),
+                        neighbor.edge,
+                    )
+                })
+            })
+            .collect();
+        edges.sort_by_key(|(from, to, _)| (*from, *to));
+        edges
+    }
+
+    fn get_cost(result: &PathfindingResult) -> Option<u32> {
+        match result {
+            Path { cost, .. } => Some(*cost),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_cave() {
+        let size = Size2d::new(60, 40);
+        let map = CaveGenerator::default().generate(size, 7);
+        let hierarchy = HierarchicalAStar::new(&map, 8);
+        let free: Vec<usize> = (0..size.get_tiles())
+            .filter(|index| map.is_valid(*index))
+            .collect();
+
+        for (start, goal) in free.iter().zip(free.iter().rev().step_by(7)) {
+            if start != goal {
+                assert_valid_path(&map, *start, &hierarchy.find(&map, *start, *goal));
+            }
+        }
+    }
+
+    #[test]
+    fn test_start_and_goal_in_same_cluster() {
+        // ########
+        // #.#.####
+        // #.#.####
+        // #.#.####
+        // #...####
+        // ########
+        let mut map = OccupancyMap::new(Size2d::new(8, 6), false);
+        map.add_border();
+        map.fill_rectangle(4, 1, 3, 4, true);
+        map.set_node(10, true);
+        map.set_node(18, true);
+        map.set_node(26, true);
+        let hierarchy = HierarchicalAStar::new(&map, 4);
+
+        assert_eq!(
+            hierarchy.find(&map, 9, 11),
+            Path {
+                indices: vec![17, 25, 33, 34, 35, 27, 19, 11],
+                cost: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn test_costs() {
+        let mut map = CostMap::new(Size2d::new(8, 1), 1);
+        map.set_cost(5, 10);
+        let hierarchy = HierarchicalAStar::new(&map, 3);
+
+        assert_eq!(
+            hierarchy.find(&map, 0, 7),
+            Path {
+                indices: vec![1, 2, 3, 4, 5, 6, 7],
+                cost: 16,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_nodes() {
+        let mut map = OccupancyMap::new(Size2d::new(6, 3), false);
+        map.add_border();
+        let hierarchy = HierarchicalAStar::new(&map, 3);
+
+        assert_eq!(hierarchy.find(&map, 7, 7), GoalAlreadyReached);
+        assert_eq!(hierarchy.find(&map, 0, 7), InvalidStart);
+        assert_eq!(hierarchy.find(&map, 7, 0), InvalidGoal);
+        assert_eq!(
+            hierarchy.find(&OccupancyMap::new(Size2d::new(2, 2), false), 0, 1),
+            NotSearched
+        );
+    }
+
+    #[test]
+    fn test_update_matches_rebuild() {
+        let size = Size2d::new(40, 30);
+        let mut map = CaveGenerator::default().generate(size, 3);
+        let mut hierarchy = HierarchicalAStar::new(&map, 6);
+        let free: Vec<usize> = (0..size.get_tiles())
+            .filter(|index| map.is_valid(*index))
+            .collect();
+        let start = free[0];
+        let goal = free[free.len() - 1];
+
+        for (i, tile) in free.iter().enumerate().skip(1).step_by(13) {
+            if *tile == goal {
+                continue;
+            }
+
+            map.set_node(*tile, i % 2 == 0);
+            hierarchy.update(&map, *tile);
+            let rebuilt = HierarchicalAStar::new(&map, 6);
+
+            assert_eq!(get_edges(&hierarchy), get_edges(&rebuilt));
+            assert_eq!(
+                get_cost(&hierarchy.find(&map, start, goal)),
+                get_cost(&rebuilt.find(&map, start, goal))
+            );
+        }
+    }
+
+    #[test]
+    fn test_open_a_wall() {
+        // ##########
+        // #...#....#
+        // ##########
+        let mut map = OccupancyMap::new(Size2d::new(10, 3), false);
+        map.add_border();
+        map.set_node(14, true);
+        let mut hierarchy = HierarchicalAStar::new(&map, 4);
+
+        assert_eq!(hierarchy.find(&map, 11, 18), NoPathFound);
+
+        map.set_node(14, false);
+        hierarchy.update(&map, 14);
+
+        assert_eq!(
+            hierarchy.find(&map, 11, 18),
+            Path {
+                indices: vec![12, 13, 14, 15, 16, 17, 18],
+                cost: 7,
+            }
+        );
+    }
+}
@@ -0,0 +1,749 @@
+use crate::algorithm::pathfinding::a_star::TieBreaking;
+use crate::algorithm::pathfinding::context::{OpenNode, SearchContext};
+use crate::algorithm::pathfinding::PathfindingResult::*;
+use crate::algorithm::pathfinding::{CostCalculator, PathfindingAlgorithm, PathfindingResult};
+use crate::math::distance::DistanceCalculator;
+use crate::math::graph::map2d::Map2d;
+use crate::math::graph::{Graph, Neighbor};
+use crate::math::size2d::Size2d;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// The cost of a straight step with [`GridMovement::Diagonal`]
+pub const STRAIGHT_COST: u32 = 10;
+/// The cost of a diagonal step with [`GridMovement::Diagonal`], which approximates `10 * sqrt(2)`
+pub const DIAGONAL_COST: u32 = 14;
+
+/// The allowed steps on a grid
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GridMovement {
+    /// 4-connected grid, where each step costs 1
+    #[default]
+    Orthogonal,
+    /// 8-connected grid, where steps cost [`STRAIGHT_COST`] or [`DIAGONAL_COST`].
+    /// Diagonal steps can not cut the corners of invalid tiles.
+    Diagonal,
+}
+
+/// The Jump Point Search algorithm for grids with uniform costs
+///
+/// It only expands the jump points of the grid instead of every tile along straight lines,
+/// which makes it much faster than [`AStar`](crate::algorithm::pathfinding::a_star::AStar) on large open maps.
+/// The costs of the graph are ignored, only [`CostCalculator::is_valid`] matters.
+///
+/// See [Harabor & Grastien](https://users.cecs.anu.edu.au/~dharabor/data/papers/harabor-grastien-aaai11.pdf)
+#[derive(Clone, Copy, Debug)]
+pub struct JumpPointSearch {
+    size: Size2d,
+    movement: GridMovement,
+}
+
+impl<N, E> PathfindingAlgorithm<N, E> for JumpPointSearch {
+    /// Finds the shortest available path from the start node to the goal node of the grid.
+    ///
+    /// The path contains every tile and not only the jump points.
+    /// Returns [`PathfindingResult::NotSearched`], if the graph doesn't match the size of the grid.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::jps::{GridMovement, JumpPointSearch};
+    ///# use rust_roguelike_core::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+    /// let mut map = OccupancyMap::new(Size2d::new(5, 4), false);
+    /// map.add_border();
+    /// map.set_node(7, true);
+    /// let algorithm = JumpPointSearch::for_map(&map, GridMovement::Orthogonal);
+    ///
+    /// assert_eq!(algorithm.find(&map, 6, 8),
+    ///            PathfindingResult::Path {
+    ///              indices: vec![11, 12, 13, 8],
+    ///              cost: 4,
+    ///            });
+    /// ```
+    fn find<G>(&self, graph: &G, start: usize, goal: usize) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+        E: Debug,
+    {
+        let mut context = SearchContext::default();
+        self.find_with_context(&mut context, graph, start, goal)
+    }
+}
+
+impl JumpPointSearch {
+    /// Creates a new JumpPointSearch for grids of a specific size
+    pub fn new(size: Size2d, movement: GridMovement) -> Self {
+        JumpPointSearch { size, movement }
+    }
+
+    /// Creates a new JumpPointSearch for the size of the map
+    pub fn for_map<N, E, M: Map2d<N, E>>(map: &M, movement: GridMovement) -> Self {
+        JumpPointSearch::new(map.get_size_2d(), movement)
+    }
+
+    /// Finds the shortest available path like [`PathfindingAlgorithm::find`],
+    /// but reuses the storage of the context to avoid allocations.
+    ///
+    /// Returns [`PathfindingResult::NotSearched`], if the graph doesn't match the size of the grid.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::algorithm::pathfinding::context::SearchContext;
+    ///# use rust_roguelike_core::algorithm::pathfinding::jps::{GridMovement, JumpPointSearch};
+    ///# use rust_roguelike_core::algorithm::pathfinding::PathfindingResult;
+    /// let mut map = OccupancyMap::new(Size2d::new(5, 5), false);
+    /// map.add_border();
+    /// let algorithm = JumpPointSearch::for_map(&map, GridMovement::Diagonal);
+    /// let mut context = SearchContext::default();
+    ///
+    /// assert_eq!(algorithm.find_with_context(&mut context, &map, 6, 18),
+    ///            PathfindingResult::Path {
+    ///              indices: vec![12, 18],
+    ///              cost: 28,
+    ///            });
+    /// ```
+    pub fn find_with_context<N, E, G>(
+        &self,
+        context: &mut SearchContext,
+        graph: &G,
+        start: usize,
+        goal: usize,
+    ) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+    {
+        let tiles = self.size.get_tiles();
+
+        if graph.get_size() != tiles {
+            return NotSearched;
+        } else if start == goal {
+            return GoalAlreadyReached;
+        } else if start >= tiles || !graph.is_valid(start) {
+            return InvalidStart;
+        } else if goal >= tiles || !graph.is_valid(goal) {
+            return InvalidGoal;
+        }
+
+        let grid = Grid::new(graph, self.size, self.movement, goal);
+
+        context.prepare(tiles);
+
+        let start_node = context.get_or_insert(start, || grid.estimate(start));
+        start_node.cost = 0;
+        start_node.total_cost = start_node.heuristic;
+        context.open_nodes.push(OpenNode::start(start));
+
+        while let Some(open_node) = context.open_nodes.pop() {
+            let node = match context.get(open_node.index) {
+                Some(node) if open_node.total_cost <= node.total_cost => *node,
+                _ => continue,
+            };
+
+            if open_node.index == goal {
+                let jump_points = context.create_path(goal);
+
+                return Path {
+                    indices: grid.connect(start, &jump_points),
+                    cost: node.cost,
+                };
+            }
+
+            for jump_point in grid.find_successors(open_node.index, node.previous) {
+                let new_cost = node.cost + grid.get_cost(open_node.index, jump_point);
+                let jump_node = context.get_or_insert(jump_point, || grid.estimate(jump_point));
+
+                if new_cost < jump_node.cost {
+                    let new_total_cost = new_cost + jump_node.heuristic;
+                    jump_node.cost = new_cost;
+                    jump_node.total_cost = new_total_cost;
+                    jump_node.previous = Some(open_node.index);
+                    context.open_nodes.push(OpenNode::new(
+                        jump_point,
+                        new_total_cost,
+                        TieBreaking::HighestCost.get_tie_breaker(new_cost, 0),
+                    ));
+                }
+            }
+        }
+
+        NoPathFound
+    }
+}
+
+/// A 2d map as the graph of a [`GridMovement`], where the edges are the steps `[dx, dy]`.
+///
+/// [`AStar`](crate::algorithm::pathfinding::a_star::AStar) finds paths with the same costs
+/// as [`JumpPointSearch`] on it, e.g. to compare both.
+///
+/// ```
+///# use rust_roguelike_core::algorithm::pathfinding::a_star::AStar;
+///# use rust_roguelike_core::algorithm::pathfinding::jps::{GridGraph, GridMovement};
+///# use rust_roguelike_core::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+///# use rust_roguelike_core::math::size2d::Size2d;
+/// let mut map = OccupancyMap::new(Size2d::new(5, 5), false);
+/// map.add_border();
+/// let grid = GridGraph::new(&map, GridMovement::Diagonal);
+///
+/// assert_eq!(AStar::default().find(&grid, 6, 18),
+///            PathfindingResult::Path {
+///              indices: vec![12, 18],
+///              cost: 28,
+///            });
+/// ```
+pub struct GridGraph<'a, M, E> {
+    map: &'a M,
+    size: Size2d,
+    movement: GridMovement,
+    phantom: PhantomData<E>,
+}
+
+impl<'a, M, E> GridGraph<'a, M, E> {
+    /// Creates a new GridGraph for the map
+    pub fn new<N>(map: &'a M, movement: GridMovement) -> Self
+    where
+        M: Map2d<N, E>,
+    {
+        GridGraph {
+            map,
+            size: map.get_size_2d(),
+            movement,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, M: CostCalculator<E>, E> GridGraph<'a, M, E> {
+    fn is_free(&self, x: i32, y: i32) -> bool {
+        x >= 0
+            && y >= 0
+            && x < self.size.width() as i32
+            && y < self.size.height() as i32
+            && self.map.is_valid(self.size.to_index(x as u32, y as u32))
+    }
+}
+
+impl<'a, N, E, M: Map2d<N, E> + CostCalculator<E>> Graph<N, [i32; 2]> for GridGraph<'a, M, E> {
+    fn get_size(&self) -> usize {
+        self.map.get_size()
+    }
+
+    fn get_node(&self, index: usize) -> Option<&N> {
+        self.map.get_node(index)
+    }
+
+    /// Returns the free neighbors. Diagonal steps can not cut corners.
+    fn get_neighbors(&self, index: usize) -> Vec<Neighbor<[i32; 2]>> {
+        let [x, y] = self.size.to_point(index);
+        let (x, y) = (x as i32, y as i32);
+        let is_diagonal = self.movement == GridMovement::Diagonal;
+        let mut neighbors = Vec::new();
+
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let is_step = if dx != 0 && dy != 0 {
+                    is_diagonal && self.is_free(x + dx, y) && self.is_free(x, y + dy)
+                } else {
+                    dx != dy
+                };
+
+                if is_step && self.is_free(x + dx, y + dy) {
+                    neighbors.push(Neighbor {
+                        index: self.size.to_index((x + dx) as u32, (y + dy) as u32),
+                        edge: [dx, dy],
+                    });
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    /// Returns the cost of the cheapest path without obstacles, ignoring the calculator
+    fn get_distance(&self, _calculator: DistanceCalculator, from: usize, to: usize) -> u32 {
+        let [from_x, from_y] = self.size.to_point(from);
+        let [to_x, to_y] = self.size.to_point(to);
+
+        get_line_cost(self.movement, from_x.abs_diff(to_x), from_y.abs_diff(to_y))
+    }
+}
+
+impl<'a, M: CostCalculator<E>, E> CostCalculator<[i32; 2]> for GridGraph<'a, M, E> {
+    fn is_valid(&self, index: usize) -> bool {
+        self.map.is_valid(index)
+    }
+
+    fn calculate_cost(&self, _index: usize, neighbor: &Neighbor<[i32; 2]>) -> u32 {
+        let [dx, dy] = neighbor.edge;
+        get_line_cost(self.movement, dx.unsigned_abs(), dy.unsigned_abs())
+    }
+}
+
+/// Returns the cost of a path, which moves `dx` & `dy` tiles without obstacles
+fn get_line_cost(movement: GridMovement, dx: u32, dy: u32) -> u32 {
+    match movement {
+        GridMovement::Orthogonal => dx + dy,
+        GridMovement::Diagonal => {
+            DIAGONAL_COST * dx.min(dy) + STRAIGHT_COST * (dx.max(dy) - dx.min(dy))
+        }
+    }
+}
+
+/// The grid view of the graph used during a single search
+struct Grid<'a, G, E> {
+    graph: &'a G,
+    width: i32,
+    height: i32,
+    movement: GridMovement,
+    goal: (i32, i32),
+    phantom: PhantomData<E>,
+}
+
+impl<'a, E, G: CostCalculator<E>> Grid<'a, G, E> {
+    fn new(graph: &'a G, size: Size2d, movement: GridMovement, goal: usize) -> Self {
+        let [x, y] = size.to_point(goal);
+
+        Grid {
+            graph,
+            width: size.width() as i32,
+            height: size.height() as i32,
+            movement,
+            goal: (x as i32, y as i32),
+            phantom: PhantomData,
+        }
+    }
+
+    fn to_point(&self, index: usize) -> (i32, i32) {
+        let width = self.width as usize;
+        ((index % width) as i32, (index / width) as i32)
+    }
+
+    fn to_index(&self, x: i32, y: i32) -> usize {
+        (y * self.width + x) as usize
+    }
+
+    fn is_free(&self, x: i32, y: i32) -> bool {
+        x >= 0
+            && y >= 0
+            && x < self.width
+            && y < self.height
+            && self.graph.is_valid(self.to_index(x, y))
+    }
+
+    /// Returns the cost between 2 tiles, which is exact for straight & diagonal lines
+    fn get_cost(&self, from: usize, to: usize) -> u32 {
+        let (from_x, from_y) = self.to_point(from);
+        let (to_x, to_y) = self.to_point(to);
+        let dx = (from_x - to_x).unsigned_abs();
+        let dy = (from_y - to_y).unsigned_abs();
+
+        get_line_cost(self.movement, dx, dy)
+    }
+
+    fn estimate(&self, index: usize) -> u32 {
+        let (x, y) = self.goal;
+        self.get_cost(index, self.to_index(x, y))
+    }
+
+    /// Returns all tiles along the lines between the jump points
+    fn connect(&self, start: usize, jump_points: &[usize]) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let (mut x, mut y) = self.to_point(start);
+
+        for jump_point in jump_points {
+            let (end_x, end_y) = self.to_point(*jump_point);
+            let dx = (end_x - x).signum();
+            let dy = (end_y - y).signum();
+
+            while (x, y) != (end_x, end_y) {
+                x += dx;
+                y += dy;
+                indices.push(self.to_index(x, y));
+            }
+        }
+
+        indices
+    }
+
+    fn find_successors(&self, index: usize, previous: Option<usize>) -> Vec<usize> {
+        let (x, y) = self.to_point(index);
+        let parent = previous.map(|previous| self.to_point(previous));
+
+        self.get_pruned_neighbors(x, y, parent)
+            .into_iter()
+            .filter_map(|(nx, ny)| self.jump(nx, ny, nx - x, ny - y))
+            .map(|(jx, jy)| self.to_index(jx, jy))
+            .collect()
+    }
+
+    /// Returns the neighbors, which can't be reached more cheaply without this tile
+    fn get_pruned_neighbors(&self, x: i32, y: i32, parent: Option<(i32, i32)>) -> Vec<(i32, i32)> {
+        let mut neighbors = Vec::new();
+
+        let (dx, dy) = match parent {
+            Some((px, py)) => ((x - px).signum(), (y - py).signum()),
+            None => {
+                for (dx, dy) in &[(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                    self.add_if_free(&mut neighbors, x + dx, y + dy);
+                }
+
+                if self.movement == GridMovement::Diagonal {
+                    for (dx, dy) in &[(1, 1), (1, -1), (-1, -1), (-1, 1)] {
+                        if self.is_free(x + dx, y) && self.is_free(x, y + dy) {
+                            self.add_if_free(&mut neighbors, x + dx, y + dy);
+                        }
+                    }
+                }
+
+                return neighbors;
+            }
+        };
+
+        match self.movement {
+            GridMovement::Orthogonal => {
+                if dx != 0 {
+                    self.add_if_free(&mut neighbors, x, y - 1);
+                    self.add_if_free(&mut neighbors, x, y + 1);
+                    self.add_if_free(&mut neighbors, x + dx, y);
+                } else {
+                    self.add_if_free(&mut neighbors, x - 1, y);
+                    self.add_if_free(&mut neighbors, x + 1, y);
+                    self.add_if_free(&mut neighbors, x, y + dy);
+                }
+            }
+            GridMovement::Diagonal if dx != 0 && dy != 0 => {
+                let is_vertical_free = self.is_free(x, y + dy);
+                let is_horizontal_free = self.is_free(x + dx, y);
+
+                if is_vertical_free {
+                    neighbors.push((x, y + dy));
+                }
+                if is_horizontal_free {
+                    neighbors.push((x + dx, y));
+                }
+                if is_vertical_free && is_horizontal_free {
+                    self.add_if_free(&mut neighbors, x + dx, y + dy);
+                }
+            }
+            GridMovement::Diagonal => {
+                // the sides are perpendicular to the movement
+                let (side_x, side_y) = (dy, dx);
+                let is_next_free = self.is_free(x + dx, y + dy);
+                let is_side0_free = self.is_free(x + side_x, y + side_y);
+                let is_side1_free = self.is_free(x - side_x, y - side_y);
+
+                if is_next_free {
+                    neighbors.push((x + dx, y + dy));
+
+                    if is_side0_free {
+                        self.add_if_free(&mut neighbors, x + dx + side_x, y + dy + side_y);
+                    }
+                    if is_side1_free {
+                        self.add_if_free(&mut neighbors, x + dx - side_x, y + dy - side_y);
+                    }
+                }
+                if is_side0_free {
+                    neighbors.push((x + side_x, y + side_y));
+                }
+                if is_side1_free {
+                    neighbors.push((x - side_x, y - side_y));
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    fn add_if_free(&self, neighbors: &mut Vec<(i32, i32)>, x: i32, y: i32) {
+        if self.is_free(x, y) {
+            neighbors.push((x, y));
+        }
+    }
+
+    /// Moves from a tile in a direction until it finds a jump point or is blocked
+    fn jump(&self, mut x: i32, mut y: i32, dx: i32, dy: i32) -> Option<(i32, i32)> {
+        loop {
+            if !self.is_free(x, y) {
+                return None;
+            } else if (x, y) == self.goal {
+                return Some((x, y));
+            }
+
+            if dx != 0 && dy != 0 {
+                if self.jump(x + dx, y, dx, 0).is_some() || self.jump(x, y + dy, 0, dy).is_some() {
+                    return Some((x, y));
+                }
+            } else if self.has_forced_neighbor(x, y, dx, dy)
+                || (self.movement == GridMovement::Orthogonal
+                    && dy != 0
+                    && (self.jump(x + 1, y, 1, 0).is_some()
+                        || self.jump(x - 1, y, -1, 0).is_some()))
+            {
+                return Some((x, y));
+            }
+
+            if self.movement == GridMovement::Diagonal
+                && !(self.is_free(x + dx, y) && self.is_free(x, y + dy))
+            {
+                return None;
+            }
+
+            x += dx;
+            y += dy;
+        }
+    }
+
+    /// Is a side of a straight line only reachable through this tile, because a wall ends behind it?
+    fn has_forced_neighbor(&self, x: i32, y: i32, dx: i32, dy: i32) -> bool {
+        let (side_x, side_y) = (dy, dx);
+
+        (self.is_free(x + side_x, y + side_y) && !self.is_free(x + side_x - dx, y + side_y - dy))
+            || (self.is_free(x - side_x, y - side_y)
+                && !self.is_free(x - side_x - dx, y - side_y - dy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::pathfinding::a_star::AStar;
+    use crate::math::graph::occupancy::OccupancyMap;
+    use crate::math::random::Random;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_no_path_found() {
+        let mut map = OccupancyMap::new(Size2d::new(5, 3), false);
+        map.add_border();
+        map.set_node(7, true);
+
+        for movement in &[GridMovement::Orthogonal, GridMovement::Diagonal] {
+            let algorithm = JumpPointSearch::for_map(&map, *movement);
+
+            assert_eq!(algorithm.find(&map, 6, 8), NoPathFound);
+        }
+    }
+
+    #[test]
+    fn test_invalid_nodes() {
+        let mut map = OccupancyMap::new(Size2d::new(3, 3), false);
+        map.add_border();
+        let algorithm = JumpPointSearch::for_map(&map, GridMovement::Orthogonal);
+
+        assert_eq!(algorithm.find(&map, 4, 4), GoalAlreadyReached);
+        assert_eq!(algorithm.find(&map, 0, 4), InvalidStart);
+        assert_eq!(algorithm.find(&map, 4, 0), InvalidGoal);
+        assert_eq!(algorithm.find(&map, 4, 9), InvalidGoal);
+    }
+
+    #[test]
+    fn test_wrong_size() {
+        let map = OccupancyMap::new(Size2d::new(3, 3), false);
+        let algorithm = JumpPointSearch::new(Size2d::new(4, 3), GridMovement::Orthogonal);
+
+        assert_eq!(algorithm.find(&map, 0, 2), NotSearched);
+    }
+
+    #[test]
+    fn test_grid_graph_neighbors() {
+        let indices = |grid: &GridGraph<OccupancyMap, _>, index| -> Vec<usize> {
+            grid.get_neighbors(index).iter().map(|n| n.index).collect()
+        };
+        let mut map = OccupancyMap::new(Size2d::new(3, 3), false);
+
+        assert_eq!(
+            indices(&GridGraph::new(&map, GridMovement::Orthogonal), 4),
+            vec![1, 3, 5, 7]
+        );
+        assert_eq!(
+            indices(&GridGraph::new(&map, GridMovement::Diagonal), 4),
+            vec![0, 1, 2, 3, 5, 6, 7, 8]
+        );
+
+        map.set_node(1, true);
+
+        assert_eq!(
+            indices(&GridGraph::new(&map, GridMovement::Diagonal), 4),
+            vec![3, 5, 6, 7, 8]
+        );
+    }
+
+    #[test]
+    fn test_no_corner_cutting() {
+        // #####
+        // #..G#
+        // #.#.#
+        // #S..#
+        // #####
+        let mut map = OccupancyMap::new(Size2d::new(5, 5), false);
+        map.add_border();
+        map.set_node(12, true);
+        let algorithm = JumpPointSearch::for_map(&map, GridMovement::Diagonal);
+
+        assert_eq!(
+            algorithm.find(&map, 6, 18),
+            Path {
+                indices: vec![11, 16, 17, 18],
+                cost: 40,
+            }
+        );
+    }
+
+    #[test]
+    fn test_orthogonal_is_optimal() {
+        let size = Size2d::new(20, 15);
+        let a_star = AStar::default();
+
+        for seed in 0..50 {
+            let (map, start, goal) = create_random_map(size, seed);
+            let algorithm = JumpPointSearch::for_map(&map, GridMovement::Orthogonal);
+            let result = algorithm.find(&map, start, goal);
+
+            assert_eq!(
+                get_cost(&result),
+                find_cost(&map, start, goal, GridMovement::Orthogonal)
+            );
+            assert_eq!(get_cost(&result), get_cost(&a_star.find(&map, start, goal)));
+            assert_valid_path(&map, start, &result, GridMovement::Orthogonal);
+        }
+    }
+
+    #[test]
+    fn test_diagonal_is_optimal() {
+        let size = Size2d::new(20, 15);
+        let a_star = AStar::default();
+
+        for seed in 0..50 {
+            let (map, start, goal) = create_random_map(size, seed);
+            let algorithm = JumpPointSearch::for_map(&map, GridMovement::Diagonal);
+            let result = algorithm.find(&map, start, goal);
+
+            assert_eq!(
+                get_cost(&result),
+                find_cost(&map, start, goal, GridMovement::Diagonal)
+            );
+            assert_eq!(
+                get_cost(&result),
+                get_cost(&a_star.find(&GridGraph::new(&map, GridMovement::Diagonal), start, goal))
+            );
+            assert_valid_path(&map, start, &result, GridMovement::Diagonal);
+        }
+    }
+
+    fn create_random_map(size: Size2d, seed: u64) -> (OccupancyMap, usize, usize) {
+        let mut random = Random::new(seed);
+        let mut map = OccupancyMap::new(size, false);
+        map.add_random_nodes(&mut random, 25);
+        map.add_border();
+
+        let free: Vec<usize> = (0..size.get_tiles())
+            .filter(|index| !map.is_occupied[*index])
+            .collect();
+        let start = *random.choose(&free).unwrap();
+        let goal = *random.choose(&free).unwrap();
+
+        (map, start, goal)
+    }
+
+    fn get_cost(result: &PathfindingResult) -> Option<u32> {
+        match result {
+            Path { cost, .. } => Some(*cost),
+            GoalAlreadyReached => Some(0),
+            _ => None,
+        }
+    }
+
+    fn get_steps(movement: GridMovement) -> Vec<(i32, i32, u32)> {
+        match movement {
+            GridMovement::Orthogonal => vec![(0, 1, 1), (1, 0, 1), (0, -1, 1), (-1, 0, 1)],
+            GridMovement::Diagonal => vec![
+                (0, 1, STRAIGHT_COST),
+                (1, 0, STRAIGHT_COST),
+                (0, -1, STRAIGHT_COST),
+                (-1, 0, STRAIGHT_COST),
+                (1, 1, DIAGONAL_COST),
+                (1, -1, DIAGONAL_COST),
+                (-1, -1, DIAGONAL_COST),
+                (-1, 1, DIAGONAL_COST),
+            ],
+        }
+    }
+
+    fn is_step_free(map: &OccupancyMap, x: i32, y: i32, dx: i32, dy: i32) -> bool {
+        let size = map.get_size_2d();
+        let is_free = |x: i32, y: i32| {
+            x >= 0
+                && y >= 0
+                && x < size.width() as i32
+                && y < size.height() as i32
+                && !map.is_occupied[size.to_index(x as u32, y as u32)]
+        };
+
+        is_free(x + dx, y + dy) && is_free(x + dx, y) && is_free(x, y + dy)
+    }
+
+    /// Brute-force Dijkstra as reference
+    fn find_cost(
+        map: &OccupancyMap,
+        start: usize,
+        goal: usize,
+        movement: GridMovement,
+    ) -> Option<u32> {
+        let size = map.get_size_2d();
+        let mut costs = vec![u32::MAX; size.get_tiles()];
+        let mut queue = VecDeque::new();
+        costs[start] = 0;
+        queue.push_back(start);
+
+        while let Some(index) = queue.pop_front() {
+            let [x, y] = size.to_point(index);
+
+            for (dx, dy, cost) in get_steps(movement) {
+                if !is_step_free(map, x as i32, y as i32, dx, dy) {
+                    continue;
+                }
+
+                let neighbor = size.to_index((x as i32 + dx) as u32, (y as i32 + dy) as u32);
+                let new_cost = costs[index] + cost;
+
+                if new_cost < costs[neighbor] {
+                    costs[neighbor] = new_cost;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        Some(costs[goal]).filter(|cost| *cost != u32::MAX)
+    }
+
+    fn assert_valid_path(
+        map: &OccupancyMap,
+        start: usize,
+        result: &PathfindingResult,
+        movement: GridMovement,
+    ) {
+        if let Path { indices, cost } = result {
+            let size = map.get_size_2d();
+            let mut previous = start;
+            let mut total = 0;
+
+            for index in indices {
+                let [x, y] = size.to_point(previous);
+                let [nx, ny] = size.to_point(*index);
+                let dx = nx as i32 - x as i32;
+                let dy = ny as i32 - y as i32;
+                let (_, _, step_cost) = get_steps(movement)
+                    .into_iter()
+                    .find(|(sx, sy, _)| (*sx, *sy) == (dx, dy))
+                    .expect("Not a neighbor");
+
+                assert!(is_step_free(map, x as i32, y as i32, dx, dy));
+                total += step_cost;
+                previous = *index;
+            }
+
+            assert_eq!(total, *cost);
+        }
+    }
+}
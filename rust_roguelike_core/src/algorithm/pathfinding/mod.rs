@@ -1,21 +1,51 @@
 pub mod a_star;
+pub mod context;
+pub mod flow_field;
+pub mod goal;
+pub mod hpa;
+pub mod jps;
+pub mod profile;
+pub mod trace;
 
+use crate::algorithm::pathfinding::profile::{MovementProfile, ProfiledGraph};
 use crate::math::graph::{Graph, Neighbor};
 use std::fmt::Debug;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum PathfindingResult {
     GoalAlreadyReached,
     InvalidStart,
     InvalidGoal,
+    #[default]
     NotSearched,
     NoPathFound,
-    Path { indices: Vec<usize> },
+    Path {
+        indices: Vec<usize>,
+        cost: u32,
+    },
+    /// The search exceeded its [`Budget`]. Contains the path to the node closest to the goal.
+    BudgetExceeded {
+        indices: Vec<usize>,
+        cost: u32,
+    },
 }
 
-impl Default for PathfindingResult {
-    fn default() -> Self {
-        PathfindingResult::NotSearched
+/// Limits the work of a single search
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// The maximum number of expanded nodes
+    pub max_nodes: Option<usize>,
+    /// The maximum cost of a path. Exceeding it is only reported, if the goal could be reached otherwise.
+    pub max_cost: Option<u32>,
+}
+
+impl Budget {
+    /// Creates a new Budget. `None` means unlimited.
+    pub fn new(max_nodes: Option<usize>, max_cost: Option<u32>) -> Self {
+        Budget {
+            max_nodes,
+            max_cost,
+        }
     }
 }
 
@@ -31,4 +61,19 @@ pub trait PathfindingAlgorithm<N, E> {
     where
         G: Graph<N, E> + CostCalculator<E>,
         E: Debug;
+
+    /// Finds the shortest available path for an agent with its own [`MovementProfile`]
+    fn find_with_profile<G>(
+        &self,
+        graph: &G,
+        profile: &dyn MovementProfile<E>,
+        start: usize,
+        goal: usize,
+    ) -> PathfindingResult
+    where
+        G: Graph<N, E> + CostCalculator<E>,
+        E: Debug,
+    {
+        self.find(&ProfiledGraph::new(graph, profile), start, goal)
+    }
 }
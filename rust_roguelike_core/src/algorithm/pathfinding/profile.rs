@@ -0,0 +1,164 @@
+use crate::algorithm::pathfinding::CostCalculator;
+use crate::math::distance::DistanceCalculator;
+use crate::math::graph::{Graph, Neighbor};
+
+/// The movement rules of a specific agent like a flying creature or a ghost.
+///
+/// It adjusts the validity & costs of the graph for this agent without modifying the graph.
+pub trait MovementProfile<E> {
+    /// Can the agent enter the node? `is_valid` is the answer of the graph itself.
+    fn is_valid(&self, _index: usize, is_valid: bool) -> bool {
+        is_valid
+    }
+
+    /// Returns the agent's cost of moving to the neighbor. `cost` is the graph's own cost.
+    fn calculate_cost(&self, _index: usize, _neighbor: &Neighbor<E>, cost: u32) -> u32 {
+        cost
+    }
+}
+
+/// Combines a graph with a [`MovementProfile`], so pathfinding uses the agent's rules.
+pub struct ProfiledGraph<'a, G, E> {
+    graph: &'a G,
+    profile: &'a dyn MovementProfile<E>,
+    size: usize,
+}
+
+impl<'a, G, E> ProfiledGraph<'a, G, E> {
+    /// Creates a new ProfiledGraph
+    pub fn new<N>(graph: &'a G, profile: &'a dyn MovementProfile<E>) -> Self
+    where
+        G: Graph<N, E>,
+    {
+        ProfiledGraph {
+            graph,
+            profile,
+            size: graph.get_size(),
+        }
+    }
+}
+
+impl<'a, N, E, G: Graph<N, E> + CostCalculator<E>> Graph<N, E> for ProfiledGraph<'a, G, E> {
+    fn get_size(&self) -> usize {
+        self.graph.get_size()
+    }
+
+    fn get_node(&self, index: usize) -> Option<&N> {
+        self.graph.get_node(index)
+    }
+
+    fn get_neighbors(&self, index: usize) -> Vec<Neighbor<E>> {
+        self.graph
+            .get_all_neighbors(index)
+            .into_iter()
+            .filter(|neighbor| self.is_valid(neighbor.index))
+            .collect()
+    }
+
+    fn get_all_neighbors(&self, index: usize) -> Vec<Neighbor<E>> {
+        self.graph.get_all_neighbors(index)
+    }
+
+    fn get_distance(&self, calculator: DistanceCalculator, from: usize, to: usize) -> u32 {
+        self.graph.get_distance(calculator, from, to)
+    }
+}
+
+impl<'a, E, G: CostCalculator<E>> CostCalculator<E> for ProfiledGraph<'a, G, E> {
+    /// Indices outside the graph are never valid, even for a permissive profile
+    fn is_valid(&self, index: usize) -> bool {
+        index < self.size && self.profile.is_valid(index, self.graph.is_valid(index))
+    }
+
+    fn calculate_cost(&self, index: usize, neighbor: &Neighbor<E>) -> u32 {
+        let cost = self.graph.calculate_cost(index, neighbor);
+        self.profile.calculate_cost(index, neighbor, cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::pathfinding::a_star::AStar;
+    use crate::algorithm::pathfinding::PathfindingAlgorithm;
+    use crate::algorithm::pathfinding::PathfindingResult::*;
+    use crate::math::graph::cost::{CostMap, IMPASSABLE};
+    use crate::math::graph::map2d::Direction2d;
+    use crate::math::graph::occupancy::OccupancyMap;
+    use crate::math::size2d::Size2d;
+
+    struct Ghost;
+
+    impl MovementProfile<Direction2d> for Ghost {
+        fn is_valid(&self, _index: usize, _is_valid: bool) -> bool {
+            true
+        }
+    }
+
+    struct Flyer {
+        water: Vec<usize>,
+    }
+
+    impl MovementProfile<Direction2d> for Flyer {
+        fn is_valid(&self, index: usize, is_valid: bool) -> bool {
+            is_valid || self.water.contains(&index)
+        }
+
+        fn calculate_cost(
+            &self,
+            _index: usize,
+            _neighbor: &Neighbor<Direction2d>,
+            cost: u32,
+        ) -> u32 {
+            if cost == IMPASSABLE {
+                1
+            } else {
+                cost
+            }
+        }
+    }
+
+    #[test]
+    fn test_ghost_passes_walls() {
+        let mut map = OccupancyMap::new(Size2d::new(5, 3), false);
+        map.add_border();
+        map.set_node(7, true);
+        let algorithm = AStar::default();
+
+        assert_eq!(algorithm.find(&map, 6, 8), NoPathFound);
+        assert_eq!(
+            algorithm.find_with_profile(&map, &Ghost, 6, 8),
+            Path {
+                indices: vec![7, 8],
+                cost: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ghost_outside_map() {
+        let map = OccupancyMap::new(Size2d::new(3, 3), false);
+        let ghost = ProfiledGraph::new(&map, &Ghost);
+
+        assert!(!ghost.is_valid(9));
+        assert_eq!(AStar::default().find(&ghost, 100, 0), InvalidStart);
+        assert_eq!(AStar::default().find(&ghost, 0, 100), InvalidGoal);
+    }
+
+    #[test]
+    fn test_flyer_ignores_water() {
+        let mut map = CostMap::new(Size2d::new(5, 1), 1);
+        map.set_cost(2, IMPASSABLE);
+        let flyer = Flyer { water: vec![2] };
+        let algorithm = AStar::default();
+
+        assert_eq!(algorithm.find(&map, 0, 4), NoPathFound);
+        assert_eq!(
+            algorithm.find_with_profile(&map, &flyer, 0, 4),
+            Path {
+                indices: vec![1, 2, 3, 4],
+                cost: 4,
+            }
+        );
+    }
+}
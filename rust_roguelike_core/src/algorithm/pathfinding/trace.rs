@@ -0,0 +1,58 @@
+use crate::algorithm::pathfinding::PathfindingResult;
+
+/// Receives the steps of a search, e.g. to visualize it in a debug overlay or to collect statistics.
+///
+/// All methods do nothing by default.
+pub trait SearchTracer {
+    /// A new search starts
+    fn on_start(&mut self, _start: usize, _goal: usize) {}
+
+    /// A node is expanded, which means its neighbors are checked
+    fn on_expand(&mut self, _index: usize, _cost: u32) {}
+
+    /// A node is added to the open set
+    fn on_push(&mut self, _index: usize, _total_cost: u32) {}
+
+    /// The search finished
+    fn on_result(&mut self, _result: &PathfindingResult) {}
+}
+
+/// Ignores all steps of a search
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoTracer;
+
+impl SearchTracer for NoTracer {}
+
+/// Records all steps of the last search
+#[derive(Clone, Debug, Default)]
+pub struct SearchRecorder {
+    pub start: usize,
+    pub goal: usize,
+    /// The expanded nodes in order
+    pub expanded: Vec<usize>,
+    /// The nodes added to the open set in order
+    pub pushed: Vec<usize>,
+    pub result: PathfindingResult,
+}
+
+impl SearchTracer for SearchRecorder {
+    fn on_start(&mut self, start: usize, goal: usize) {
+        self.start = start;
+        self.goal = goal;
+        self.expanded.clear();
+        self.pushed.clear();
+        self.result = PathfindingResult::NotSearched;
+    }
+
+    fn on_expand(&mut self, index: usize, _cost: u32) {
+        self.expanded.push(index);
+    }
+
+    fn on_push(&mut self, index: usize, _total_cost: u32) {
+        self.pushed.push(index);
+    }
+
+    fn on_result(&mut self, result: &PathfindingResult) {
+        self.result = result.clone();
+    }
+}
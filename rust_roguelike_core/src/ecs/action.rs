@@ -0,0 +1,617 @@
+use crate::algorithm::pathfinding::CostCalculator;
+use crate::ecs::entity::Entity;
+use crate::ecs::position::Position;
+use crate::ecs::storage::{DenseStorage, SparseStorage};
+use crate::ecs::{Component, World};
+use crate::math::graph::map2d::Direction2d;
+use crate::math::graph::Graph;
+use crate::math::random::dice::Dice;
+use crate::math::random::Random;
+use std::fmt;
+
+/// The hit points of a creature. Creatures block each other's movement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Health(pub i32);
+
+impl Component for Health {
+    type Storage = DenseStorage<Self>;
+}
+
+/// Marks an entity, which can be picked up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Item;
+
+impl Component for Item {
+    type Storage = SparseStorage<Self>;
+}
+
+/// The items carried by an entity
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Inventory(pub Vec<Entity>);
+
+impl Component for Inventory {
+    type Storage = SparseStorage<Self>;
+}
+
+/// Everything an action can read or change
+pub struct ActionContext<'a, G> {
+    pub map: &'a G,
+    pub world: &'a mut World,
+    pub random: &'a mut Random,
+}
+
+impl<'a, G> ActionContext<'a, G> {
+    /// Creates a new ActionContext
+    pub fn new(map: &'a G, world: &'a mut World, random: &'a mut Random) -> Self {
+        ActionContext { map, world, random }
+    }
+}
+
+/// The reasons why an action can not be performed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionError {
+    /// The entity was destroyed
+    DeadEntity,
+    /// The entity has no [`Position`]
+    NoPosition,
+    /// The map or a creature blocks the tile
+    Blocked,
+    /// The target is not on a neighboring tile
+    NotAdjacent,
+    /// The target has no [`Health`]
+    NoHealth,
+    /// The target is no [`Item`] or lies on another tile
+    NoItemHere,
+    /// The entity has no [`Inventory`]
+    NoInventory,
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            ActionError::DeadEntity => "the entity is dead",
+            ActionError::NoPosition => "the entity has no position",
+            ActionError::Blocked => "the way is blocked",
+            ActionError::NotAdjacent => "the target is not adjacent",
+            ActionError::NoHealth => "the target has no health",
+            ActionError::NoItemHere => "there is no such item here",
+            ActionError::NoInventory => "the entity has no inventory",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// A move in the game, which is validated against the map & then applied to the world
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Move {
+        entity: Entity,
+        direction: Direction2d,
+    },
+    Attack {
+        attacker: Entity,
+        target: Entity,
+        damage: Dice,
+    },
+    PickUp {
+        entity: Entity,
+        item: Entity,
+    },
+    Wait {
+        entity: Entity,
+    },
+}
+
+/// What an action changed, so it can be undone
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Moved { from: usize, to: usize },
+    Hit { damage: i32 },
+    PickedUp { tile: usize },
+    Waited,
+}
+
+impl Action {
+    /// Returns the entity performing the action
+    pub fn get_actor(&self) -> Entity {
+        match self {
+            Action::Move { entity, .. } => *entity,
+            Action::Attack { attacker, .. } => *attacker,
+            Action::PickUp { entity, .. } => *entity,
+            Action::Wait { entity } => *entity,
+        }
+    }
+
+    /// Checks, if the action can be performed.
+    /// Returns the target tile of a move & the actor's tile otherwise.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::World;
+    ///# use rust_roguelike_core::ecs::action::{Action, ActionContext, ActionError};
+    ///# use rust_roguelike_core::ecs::position::Position;
+    ///# use rust_roguelike_core::math::graph::map2d::Direction2d;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::random::Random;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut map = OccupancyMap::new(Size2d::new(3, 1), false);
+    /// map.set_node(2, true);
+    /// let mut world = World::default();
+    /// let mut random = Random::new(42);
+    /// let player = world.create_entity();
+    /// world.insert(player, Position::new(1));
+    /// let context = ActionContext::new(&map, &mut world, &mut random);
+    ///
+    /// assert_eq!(Action::Move { entity: player, direction: Direction2d::WEST }.validate(&context), Ok(0));
+    /// assert_eq!(Action::Move { entity: player, direction: Direction2d::EAST }.validate(&context), Err(ActionError::Blocked));
+    /// assert_eq!(Action::Move { entity: player, direction: Direction2d::NORTH }.validate(&context), Err(ActionError::Blocked));
+    /// ```
+    pub fn validate<N, G>(&self, context: &ActionContext<G>) -> Result<usize, ActionError>
+    where
+        G: Graph<N, Direction2d> + CostCalculator<Direction2d>,
+    {
+        let world = &*context.world;
+        let tile = get_tile(world, self.get_actor())?;
+
+        match self {
+            Action::Move { direction, .. } => {
+                let target = context
+                    .map
+                    .get_neighbors(tile)
+                    .into_iter()
+                    .find(|neighbor| neighbor.edge == *direction)
+                    .map(|neighbor| neighbor.index)
+                    .ok_or(ActionError::Blocked)?;
+
+                let is_blocked = !context.map.is_valid(target)
+                    || world
+                        .query2::<Position, Health>()
+                        .iter()
+                        .any(|(_, position, _)| position.index == target);
+
+                if is_blocked {
+                    Err(ActionError::Blocked)
+                } else {
+                    Ok(target)
+                }
+            }
+            Action::Attack { target, .. } => {
+                let target_tile = get_tile(world, *target)?;
+
+                if !world.has::<Health>(*target) {
+                    Err(ActionError::NoHealth)
+                } else if context
+                    .map
+                    .get_neighbors(tile)
+                    .iter()
+                    .all(|neighbor| neighbor.index != target_tile)
+                {
+                    Err(ActionError::NotAdjacent)
+                } else {
+                    Ok(tile)
+                }
+            }
+            Action::PickUp { entity, item } => {
+                if !world.has::<Inventory>(*entity) {
+                    Err(ActionError::NoInventory)
+                } else if !world.has::<Item>(*item)
+                    || world.get::<Position>(*item) != Some(&Position::new(tile))
+                {
+                    Err(ActionError::NoItemHere)
+                } else {
+                    Ok(tile)
+                }
+            }
+            Action::Wait { .. } => Ok(tile),
+        }
+    }
+
+    /// Validates & performs the action
+    pub fn apply<N, G>(&self, context: &mut ActionContext<G>) -> Result<Outcome, ActionError>
+    where
+        G: Graph<N, Direction2d> + CostCalculator<Direction2d>,
+    {
+        let tile = self.validate(context)?;
+
+        let outcome = match self {
+            Action::Move { entity, .. } => {
+                let from = get_tile(context.world, *entity)?;
+                context.world.insert(*entity, Position::new(tile));
+                Outcome::Moved { from, to: tile }
+            }
+            Action::Attack { target, damage, .. } => {
+                let damage = damage.roll(context.random).total.max(0);
+
+                if let Some(health) = context.world.get_mut::<Health>(*target) {
+                    health.0 -= damage;
+                }
+
+                Outcome::Hit { damage }
+            }
+            Action::PickUp { entity, item } => {
+                context.world.remove::<Position>(*item);
+
+                if let Some(inventory) = context.world.get_mut::<Inventory>(*entity) {
+                    inventory.0.push(*item);
+                }
+
+                Outcome::PickedUp { tile }
+            }
+            Action::Wait { .. } => Outcome::Waited,
+        };
+
+        Ok(outcome)
+    }
+
+    /// Reverts the changes of the action. The random number generator is not reverted.
+    pub fn undo(&self, outcome: &Outcome, world: &mut World) {
+        match (self, outcome) {
+            (Action::Move { entity, .. }, Outcome::Moved { from, .. }) => {
+                world.insert(*entity, Position::new(*from));
+            }
+            (Action::Attack { target, .. }, Outcome::Hit { damage }) => {
+                if let Some(health) = world.get_mut::<Health>(*target) {
+                    health.0 += damage;
+                }
+            }
+            (Action::PickUp { entity, item }, Outcome::PickedUp { tile }) => {
+                if let Some(inventory) = world.get_mut::<Inventory>(*entity) {
+                    inventory.0.retain(|e| e != item);
+                }
+
+                world.insert(*item, Position::new(*tile));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A performed action with everything needed to undo it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionRecord {
+    pub action: Action,
+    pub outcome: Outcome,
+    /// The state of the random number generator before the action
+    pub random_state: u64,
+}
+
+/// Records all performed actions, so they can be undone or replayed
+///
+/// ```
+///# use rust_roguelike_core::ecs::World;
+///# use rust_roguelike_core::ecs::action::{Action, ActionContext, ActionLog};
+///# use rust_roguelike_core::ecs::position::Position;
+///# use rust_roguelike_core::math::graph::map2d::Direction2d;
+///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+///# use rust_roguelike_core::math::random::Random;
+///# use rust_roguelike_core::math::size2d::Size2d;
+/// let map = OccupancyMap::new(Size2d::new(3, 3), false);
+/// let mut world = World::default();
+/// let mut random = Random::new(42);
+/// let player = world.create_entity();
+/// world.insert(player, Position::new(4));
+/// let mut context = ActionContext::new(&map, &mut world, &mut random);
+/// let mut log = ActionLog::default();
+///
+/// assert!(log.execute(Action::Move { entity: player, direction: Direction2d::EAST }, &mut context).is_ok());
+/// assert!(log.execute(Action::Move { entity: player, direction: Direction2d::EAST }, &mut context).is_err());
+/// assert_eq!(log.len(), 1);
+/// assert_eq!(context.world.get::<Position>(player), Some(&Position::new(5)));
+///
+/// log.undo(&mut context);
+///
+/// assert!(log.is_empty());
+/// assert_eq!(context.world.get::<Position>(player), Some(&Position::new(4)));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActionLog {
+    records: Vec<ActionRecord>,
+}
+
+impl ActionLog {
+    /// Performs an action & records it, if it was valid
+    pub fn execute<N, G>(
+        &mut self,
+        action: Action,
+        context: &mut ActionContext<G>,
+    ) -> Result<Outcome, ActionError>
+    where
+        G: Graph<N, Direction2d> + CostCalculator<Direction2d>,
+    {
+        let random_state = context.random.get_state();
+        let outcome = action.apply(context)?;
+
+        self.records.push(ActionRecord {
+            action,
+            outcome,
+            random_state,
+        });
+
+        Ok(outcome)
+    }
+
+    /// Undoes the last action & restores the random number generator to the state before it
+    pub fn undo<G>(&mut self, context: &mut ActionContext<G>) -> Option<ActionRecord> {
+        let record = self.records.pop()?;
+
+        record.action.undo(&record.outcome, context.world);
+        *context.random = Random::from_state(record.random_state);
+
+        Some(record)
+    }
+
+    /// Performs the actions in order, starting from the same world & seed as the original session.
+    ///
+    /// Stops at the first invalid action & returns its index & error.
+    pub fn replay<N, G>(
+        actions: &[Action],
+        context: &mut ActionContext<G>,
+    ) -> Result<ActionLog, (usize, ActionError)>
+    where
+        G: Graph<N, Direction2d> + CostCalculator<Direction2d>,
+    {
+        let mut log = ActionLog::default();
+
+        for (i, action) in actions.iter().enumerate() {
+            log.execute(*action, context).map_err(|error| (i, error))?;
+        }
+
+        Ok(log)
+    }
+
+    pub fn get_records(&self) -> &[ActionRecord] {
+        &self.records
+    }
+
+    /// Returns the recorded actions, which can be replayed
+    pub fn get_actions(&self) -> Vec<Action> {
+        self.records.iter().map(|record| record.action).collect()
+    }
+
+    /// Returns the number of recorded actions
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+fn get_tile(world: &World, entity: Entity) -> Result<usize, ActionError> {
+    if !world.is_alive(entity) {
+        return Err(ActionError::DeadEntity);
+    }
+
+    world
+        .get::<Position>(entity)
+        .map(|position| position.index)
+        .ok_or(ActionError::NoPosition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::graph::occupancy::OccupancyMap;
+    use crate::math::size2d::Size2d;
+
+    struct Session {
+        map: OccupancyMap,
+        world: World,
+        random: Random,
+        player: Entity,
+        orc: Entity,
+        potion: Entity,
+    }
+
+    // #######
+    // #@.!.o#
+    // #######
+    fn create_session() -> Session {
+        let mut map = OccupancyMap::new(Size2d::new(7, 3), false);
+        map.add_border();
+        let mut world = World::default();
+        let player = world.create_entity();
+        world.insert(player, Position::new(8));
+        world.insert(player, Health(20));
+        world.insert(player, Inventory::default());
+        let potion = world.create_entity();
+        world.insert(potion, Position::new(10));
+        world.insert(potion, Item);
+        let orc = world.create_entity();
+        world.insert(orc, Position::new(12));
+        world.insert(orc, Health(30));
+
+        Session {
+            map,
+            world,
+            random: Random::new(1234),
+            player,
+            orc,
+            potion,
+        }
+    }
+
+    fn create_actions(session: &Session) -> Vec<Action> {
+        let damage: Dice = "2d6+1".parse().unwrap();
+        let east = Action::Move {
+            entity: session.player,
+            direction: Direction2d::EAST,
+        };
+
+        vec![
+            east,
+            east,
+            Action::PickUp {
+                entity: session.player,
+                item: session.potion,
+            },
+            east,
+            Action::Attack {
+                attacker: session.player,
+                target: session.orc,
+                damage,
+            },
+            Action::Wait {
+                entity: session.orc,
+            },
+            Action::Attack {
+                attacker: session.player,
+                target: session.orc,
+                damage,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_invalid_actions() {
+        let mut session = create_session();
+        let (player, orc, potion) = (session.player, session.orc, session.potion);
+        let context = ActionContext::new(&session.map, &mut session.world, &mut session.random);
+        let damage: Dice = "1d4".parse().unwrap();
+
+        assert_eq!(
+            Action::Attack {
+                attacker: player,
+                target: orc,
+                damage,
+            }
+            .validate(&context),
+            Err(ActionError::NotAdjacent)
+        );
+        assert_eq!(
+            Action::Attack {
+                attacker: player,
+                target: potion,
+                damage,
+            }
+            .validate(&context),
+            Err(ActionError::NoHealth)
+        );
+        assert_eq!(
+            Action::PickUp {
+                entity: player,
+                item: potion,
+            }
+            .validate(&context),
+            Err(ActionError::NoItemHere)
+        );
+        assert_eq!(
+            Action::PickUp {
+                entity: orc,
+                item: potion,
+            }
+            .validate(&context),
+            Err(ActionError::NoInventory)
+        );
+
+        context.world.destroy_entity(orc);
+
+        assert_eq!(
+            Action::Wait { entity: orc }.validate(&context),
+            Err(ActionError::DeadEntity)
+        );
+    }
+
+    #[test]
+    fn test_creatures_block_movement() {
+        let mut session = create_session();
+        let (player, orc) = (session.player, session.orc);
+        session.world.insert(player, Position::new(11));
+        let context = ActionContext::new(&session.map, &mut session.world, &mut session.random);
+
+        assert_eq!(
+            Action::Move {
+                entity: player,
+                direction: Direction2d::EAST,
+            }
+            .validate(&context),
+            Err(ActionError::Blocked)
+        );
+        assert_eq!(
+            Action::Move {
+                entity: orc,
+                direction: Direction2d::EAST,
+            }
+            .validate(&context),
+            Err(ActionError::Blocked)
+        );
+    }
+
+    #[test]
+    fn test_replay_is_deterministic() {
+        let mut original = create_session();
+        let actions = create_actions(&original);
+        let mut context =
+            ActionContext::new(&original.map, &mut original.world, &mut original.random);
+        let log = ActionLog::replay(&actions, &mut context).unwrap();
+
+        let mut replayed = create_session();
+        let mut context =
+            ActionContext::new(&replayed.map, &mut replayed.world, &mut replayed.random);
+        let replayed_log = ActionLog::replay(&log.get_actions(), &mut context).unwrap();
+
+        assert_eq!(log, replayed_log);
+        assert_eq!(
+            original.world.get::<Health>(original.orc),
+            replayed.world.get::<Health>(replayed.orc)
+        );
+        assert_eq!(original.random, replayed.random);
+        assert_eq!(
+            replayed.world.get::<Inventory>(replayed.player),
+            Some(&Inventory(vec![replayed.potion]))
+        );
+        assert_eq!(replayed.world.get::<Position>(replayed.potion), None);
+        assert_eq!(
+            replayed.world.get::<Position>(replayed.player),
+            Some(&Position::new(11))
+        );
+    }
+
+    #[test]
+    fn test_replay_stops_at_invalid_action() {
+        let mut session = create_session();
+        let mut actions = create_actions(&session);
+        actions.insert(
+            1,
+            Action::Move {
+                entity: session.player,
+                direction: Direction2d::NORTH,
+            },
+        );
+        let mut context = ActionContext::new(&session.map, &mut session.world, &mut session.random);
+
+        assert_eq!(
+            ActionLog::replay(&actions, &mut context),
+            Err((1, ActionError::Blocked))
+        );
+    }
+
+    #[test]
+    fn test_undo_everything() {
+        let mut session = create_session();
+        let actions = create_actions(&session);
+        let mut context = ActionContext::new(&session.map, &mut session.world, &mut session.random);
+        let mut log = ActionLog::replay(&actions, &mut context).unwrap();
+
+        assert!(context.world.get::<Health>(session.orc).unwrap().0 < 30);
+
+        while log.undo(&mut context).is_some() {}
+
+        let initial = create_session();
+
+        assert_eq!(context.world.get::<Health>(session.orc), Some(&Health(30)));
+        assert_eq!(
+            context.world.get::<Position>(session.player),
+            Some(&Position::new(8))
+        );
+        assert_eq!(
+            context.world.get::<Position>(session.potion),
+            Some(&Position::new(10))
+        );
+        assert_eq!(
+            context.world.get::<Inventory>(session.player),
+            Some(&Inventory::default())
+        );
+        assert_eq!(*context.random, initial.random);
+    }
+}
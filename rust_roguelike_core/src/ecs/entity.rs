@@ -0,0 +1,129 @@
+/// Identifies an entity like a monster, an item or the player.
+///
+/// The generation distinguishes it from destroyed entities, which had the same index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    /// Returns the index, which can be reused after the entity was destroyed
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// Returns how often the index was used before
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// Creates & destroys entities, while reusing the indices of destroyed ones
+#[derive(Clone, Debug, Default)]
+pub struct EntityAllocator {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free_indices: Vec<u32>,
+}
+
+impl EntityAllocator {
+    /// Creates a new entity
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::entity::EntityAllocator;
+    /// let mut allocator = EntityAllocator::default();
+    /// let first = allocator.create();
+    /// allocator.destroy(first);
+    /// let second = allocator.create();
+    ///
+    /// assert_eq!(first.index(), second.index());
+    /// assert_ne!(first, second);
+    /// assert!(!allocator.is_alive(first));
+    /// assert!(allocator.is_alive(second));
+    /// ```
+    pub fn create(&mut self) -> Entity {
+        match self.free_indices.pop() {
+            Some(index) => {
+                self.alive[index as usize] = true;
+                Entity {
+                    index,
+                    generation: self.generations[index as usize],
+                }
+            }
+            None => {
+                self.generations.push(0);
+                self.alive.push(true);
+                Entity {
+                    index: (self.generations.len() - 1) as u32,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Destroys an entity. Returns false, if it was already destroyed.
+    pub fn destroy(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        self.alive[entity.index()] = false;
+        self.generations[entity.index()] += 1;
+        self.free_indices.push(entity.index);
+        true
+    }
+
+    /// Is the entity alive?
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.alive.get(entity.index()).cloned().unwrap_or(false)
+            && self.generations[entity.index()] == entity.generation
+    }
+
+    /// Returns all living entities
+    pub fn get_entities(&self) -> Vec<Entity> {
+        self.alive
+            .iter()
+            .enumerate()
+            .filter(|(_, alive)| **alive)
+            .map(|(index, _)| Entity {
+                index: index as u32,
+                generation: self.generations[index],
+            })
+            .collect()
+    }
+
+    /// Returns the number of living entities
+    pub fn get_count(&self) -> usize {
+        self.alive.len() - self.free_indices.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destroy_twice() {
+        let mut allocator = EntityAllocator::default();
+        let entity = allocator.create();
+
+        assert!(allocator.destroy(entity));
+        assert!(!allocator.destroy(entity));
+        assert_eq!(allocator.get_count(), 0);
+    }
+
+    #[test]
+    fn test_get_entities() {
+        let mut allocator = EntityAllocator::default();
+        let a = allocator.create();
+        let b = allocator.create();
+        let c = allocator.create();
+        allocator.destroy(b);
+        let d = allocator.create();
+
+        assert_eq!(d.generation(), 1);
+        assert_eq!(allocator.get_entities(), vec![a, d, c]);
+        assert_eq!(allocator.get_count(), 3);
+    }
+}
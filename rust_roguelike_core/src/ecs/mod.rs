@@ -0,0 +1,362 @@
+pub mod action;
+pub mod entity;
+pub mod position;
+pub mod scheduler;
+pub mod spatial;
+pub mod storage;
+pub mod system;
+
+use crate::ecs::entity::{Entity, EntityAllocator};
+use crate::ecs::storage::{AnyStorage, Storage};
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// A type of data, which can be attached to entities
+///
+/// ```
+///# use rust_roguelike_core::ecs::Component;
+///# use rust_roguelike_core::ecs::storage::{DenseStorage, SparseStorage};
+/// struct Health(u32);
+///
+/// impl Component for Health {
+///     type Storage = DenseStorage<Self>;
+/// }
+///
+/// struct Burning;
+///
+/// impl Component for Burning {
+///     type Storage = SparseStorage<Self>;
+/// }
+/// ```
+pub trait Component: Sized + 'static {
+    /// How the components of this type are stored
+    type Storage: Storage<Self> + AnyStorage;
+}
+
+/// Contains all entities & their components
+#[derive(Default)]
+pub struct World {
+    entities: EntityAllocator,
+    storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+}
+
+impl World {
+    /// Creates a new entity without components
+    pub fn create_entity(&mut self) -> Entity {
+        self.entities.create()
+    }
+
+    /// Destroys an entity & removes all its components. Returns false, if it was already destroyed.
+    pub fn destroy_entity(&mut self, entity: Entity) -> bool {
+        if !self.entities.destroy(entity) {
+            return false;
+        }
+
+        for storage in self.storages.values_mut() {
+            storage.remove_entity(entity);
+        }
+
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+    }
+
+    /// Returns all living entities
+    pub fn get_entities(&self) -> Vec<Entity> {
+        self.entities.get_entities()
+    }
+
+    /// Adds a component to an entity and returns the replaced one.
+    ///
+    /// Destroyed entities get no components.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::{Component, World};
+    ///# use rust_roguelike_core::ecs::storage::DenseStorage;
+    /// #[derive(Debug, PartialEq)]
+    /// struct Health(u32);
+    ///
+    /// impl Component for Health {
+    ///     type Storage = DenseStorage<Self>;
+    /// }
+    ///
+    /// let mut world = World::default();
+    /// let orc = world.create_entity();
+    ///
+    /// assert_eq!(world.insert(orc, Health(10)), None);
+    /// assert_eq!(world.insert(orc, Health(8)), Some(Health(10)));
+    /// assert_eq!(world.get::<Health>(orc), Some(&Health(8)));
+    ///
+    /// world.destroy_entity(orc);
+    ///
+    /// assert_eq!(world.get::<Health>(orc), None);
+    /// ```
+    pub fn insert<T: Component>(&mut self, entity: Entity, component: T) -> Option<T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+
+        self.get_storage_mut::<T>().insert(entity, component)
+    }
+
+    /// Removes a component from an entity
+    pub fn remove<T: Component>(&mut self, entity: Entity) -> Option<T> {
+        self.find_storage_mut::<T>()?.remove(entity)
+    }
+
+    pub fn get<T: Component>(&self, entity: Entity) -> Option<&T> {
+        self.get_storage::<T>()?.get(entity)
+    }
+
+    pub fn get_mut<T: Component>(&mut self, entity: Entity) -> Option<&mut T> {
+        self.find_storage_mut::<T>()?.get_mut(entity)
+    }
+
+    /// Does the entity have a component of this type?
+    pub fn has<T: Component>(&self, entity: Entity) -> bool {
+        self.get::<T>(entity).is_some()
+    }
+
+    /// Returns the storage of a component type, if any component of this type was added
+    pub fn get_storage<T: Component>(&self) -> Option<&T::Storage> {
+        self.storages
+            .get(&TypeId::of::<T>())
+            .and_then(|storage| storage.as_any().downcast_ref::<T::Storage>())
+    }
+
+    /// Returns the storage of a component type & creates it if needed
+    pub fn get_storage_mut<T: Component>(&mut self) -> &mut T::Storage {
+        self.storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(T::Storage::default()))
+            .as_any_mut()
+            .downcast_mut::<T::Storage>()
+            .expect("Storage has the wrong type!")
+    }
+
+    /// Returns the storage of a component type without creating it
+    fn find_storage_mut<T: Component>(&mut self) -> Option<&mut T::Storage> {
+        self.storages
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|storage| storage.as_any_mut().downcast_mut::<T::Storage>())
+    }
+
+    /// Returns all entities with a component of this type
+    pub fn query<A: Component>(&self) -> Vec<(Entity, &A)> {
+        match self.get_storage::<A>() {
+            Some(storage) => storage
+                .get_entities()
+                .into_iter()
+                .filter_map(|entity| storage.get(entity).map(|a| (entity, a)))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns all entities with components of both types
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::{Component, World};
+    ///# use rust_roguelike_core::ecs::storage::{DenseStorage, SparseStorage};
+    /// struct Health(u32);
+    ///
+    /// impl Component for Health {
+    ///     type Storage = DenseStorage<Self>;
+    /// }
+    ///
+    /// struct Burning(u32);
+    ///
+    /// impl Component for Burning {
+    ///     type Storage = SparseStorage<Self>;
+    /// }
+    ///
+    /// let mut world = World::default();
+    /// let orc = world.create_entity();
+    /// let goblin = world.create_entity();
+    /// world.insert(orc, Health(10));
+    /// world.insert(goblin, Health(5));
+    /// world.insert(goblin, Burning(2));
+    ///
+    /// let burning = world.query2::<Health, Burning>();
+    ///
+    /// assert_eq!(burning.len(), 1);
+    /// assert_eq!(burning[0].0, goblin);
+    /// assert_eq!(burning[0].1 .0, 5);
+    /// assert_eq!(burning[0].2 .0, 2);
+    /// ```
+    pub fn query2<A: Component, B: Component>(&self) -> Vec<(Entity, &A, &B)> {
+        self.query::<A>()
+            .into_iter()
+            .filter_map(|(entity, a)| self.get::<B>(entity).map(|b| (entity, a, b)))
+            .collect()
+    }
+
+    /// Returns all entities with components of all 3 types
+    pub fn query3<A: Component, B: Component, C: Component>(&self) -> Vec<(Entity, &A, &B, &C)> {
+        self.query2::<A, B>()
+            .into_iter()
+            .filter_map(|(entity, a, b)| self.get::<C>(entity).map(|c| (entity, a, b, c)))
+            .collect()
+    }
+
+    /// Returns the entities with a component of this type, so their components can be changed
+    pub fn query_entities<A: Component>(&self) -> Vec<Entity> {
+        self.get_storage::<A>()
+            .map(|storage| storage.get_entities())
+            .unwrap_or_default()
+    }
+
+    /// Returns the entities with components of both types, so their components can be changed
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::{Component, World};
+    ///# use rust_roguelike_core::ecs::storage::{DenseStorage, SparseStorage};
+    /// struct Health(u32);
+    ///
+    /// impl Component for Health {
+    ///     type Storage = DenseStorage<Self>;
+    /// }
+    ///
+    /// struct Burning(u32);
+    ///
+    /// impl Component for Burning {
+    ///     type Storage = SparseStorage<Self>;
+    /// }
+    ///
+    /// let mut world = World::default();
+    /// let orc = world.create_entity();
+    /// let goblin = world.create_entity();
+    /// world.insert(orc, Health(10));
+    /// world.insert(goblin, Health(5));
+    /// world.insert(goblin, Burning(2));
+    ///
+    /// for entity in world.query_entities2::<Health, Burning>() {
+    ///     let damage = world.get::<Burning>(entity).unwrap().0;
+    ///     world.get_mut::<Health>(entity).unwrap().0 -= damage;
+    /// }
+    ///
+    /// assert_eq!(world.get::<Health>(orc).unwrap().0, 10);
+    /// assert_eq!(world.get::<Health>(goblin).unwrap().0, 3);
+    /// ```
+    pub fn query_entities2<A: Component, B: Component>(&self) -> Vec<Entity> {
+        self.query2::<A, B>()
+            .into_iter()
+            .map(|(entity, _, _)| entity)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::storage::{DenseStorage, SparseStorage};
+
+    #[derive(Debug, PartialEq)]
+    struct Health(u32);
+
+    impl Component for Health {
+        type Storage = DenseStorage<Self>;
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Name(&'static str);
+
+    impl Component for Name {
+        type Storage = DenseStorage<Self>;
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Burning;
+
+    impl Component for Burning {
+        type Storage = SparseStorage<Self>;
+    }
+
+    #[test]
+    fn test_destroy_removes_components() {
+        let mut world = World::default();
+        let orc = world.create_entity();
+        world.insert(orc, Health(10));
+        world.insert(orc, Burning);
+
+        assert!(world.destroy_entity(orc));
+        assert!(!world.destroy_entity(orc));
+
+        let goblin = world.create_entity();
+
+        assert_eq!(goblin.index(), orc.index());
+        assert!(!world.has::<Health>(goblin));
+        assert!(!world.has::<Burning>(goblin));
+        assert!(world.query::<Burning>().is_empty());
+        assert_eq!(world.get_entities(), vec![goblin]);
+    }
+
+    #[test]
+    fn test_insert_into_destroyed_entity() {
+        let mut world = World::default();
+        let orc = world.create_entity();
+        world.destroy_entity(orc);
+
+        assert_eq!(world.insert(orc, Health(10)), None);
+        assert!(world.query::<Health>().is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let mut world = World::default();
+        let orc = world.create_entity();
+        let goblin = world.create_entity();
+        let troll = world.create_entity();
+        world.insert(orc, Health(10));
+        world.insert(orc, Name("orc"));
+        world.insert(goblin, Name("goblin"));
+        world.insert(troll, Burning);
+        world.insert(troll, Health(20));
+        world.insert(troll, Name("troll"));
+
+        assert_eq!(
+            world.query::<Name>(),
+            vec![
+                (orc, &Name("orc")),
+                (goblin, &Name("goblin")),
+                (troll, &Name("troll"))
+            ]
+        );
+        assert_eq!(
+            world.query2::<Health, Name>(),
+            vec![
+                (orc, &Health(10), &Name("orc")),
+                (troll, &Health(20), &Name("troll"))
+            ]
+        );
+        assert_eq!(
+            world.query3::<Burning, Health, Name>(),
+            vec![(troll, &Burning, &Health(20), &Name("troll"))]
+        );
+        assert_eq!(world.query_entities::<Burning>(), vec![troll]);
+        assert_eq!(world.query_entities2::<Name, Burning>(), vec![troll]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut world = World::default();
+        let orc = world.create_entity();
+        world.insert(orc, Burning);
+
+        assert_eq!(world.remove::<Burning>(orc), Some(Burning));
+        assert_eq!(world.remove::<Health>(orc), None);
+        assert!(world.get_storage::<Burning>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lookups_do_not_create_storages() {
+        let mut world = World::default();
+        let orc = world.create_entity();
+
+        assert_eq!(world.remove::<Health>(orc), None);
+        assert_eq!(world.get_mut::<Health>(orc), None);
+        assert!(world.get_storage::<Health>().is_none());
+    }
+}
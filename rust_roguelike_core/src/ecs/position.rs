@@ -0,0 +1,82 @@
+use crate::ecs::entity::Entity;
+use crate::ecs::storage::DenseStorage;
+use crate::ecs::{Component, World};
+use crate::math::graph::map2d::Map2d;
+
+/// The tile of a [`Map2d`] an entity is standing on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub index: usize,
+}
+
+impl Component for Position {
+    type Storage = DenseStorage<Self>;
+}
+
+impl Position {
+    /// Creates a new Position from the index of a tile
+    pub fn new(index: usize) -> Self {
+        Position { index }
+    }
+
+    /// Creates a new Position from a point or returns `None`, if it is outside the map
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::position::Position;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = OccupancyMap::new(Size2d::new(4, 3), false);
+    ///
+    /// assert_eq!(Position::from_point(&map, 1, 2), Some(Position::new(9)));
+    /// assert_eq!(Position::from_point(&map, 4, 2), None);
+    /// ```
+    pub fn from_point<N, E, M: Map2d<N, E>>(map: &M, x: u32, y: u32) -> Option<Self> {
+        let size = map.get_size_2d();
+
+        if x < size.width() && y < size.height() {
+            Some(Position::new(size.to_index(x, y)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the point of the tile
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::position::Position;
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = OccupancyMap::new(Size2d::new(4, 3), false);
+    ///
+    /// assert_eq!(Position::new(9).get_point(&map), [1, 2]);
+    /// ```
+    pub fn get_point<N, E, M: Map2d<N, E>>(&self, map: &M) -> [u32; 2] {
+        map.get_size_2d().to_point(self.index)
+    }
+}
+
+impl World {
+    /// Returns all entities standing on a tile
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::World;
+    ///# use rust_roguelike_core::ecs::position::Position;
+    /// let mut world = World::default();
+    /// let player = world.create_entity();
+    /// let item = world.create_entity();
+    /// let monster = world.create_entity();
+    /// world.insert(player, Position::new(5));
+    /// world.insert(item, Position::new(5));
+    /// world.insert(monster, Position::new(7));
+    ///
+    /// assert_eq!(world.get_entities_at(5), vec![player, item]);
+    /// assert!(world.get_entities_at(6).is_empty());
+    /// ```
+    pub fn get_entities_at(&self, tile: usize) -> Vec<Entity> {
+        self.query::<Position>()
+            .into_iter()
+            .filter(|(_, position)| position.index == tile)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+}
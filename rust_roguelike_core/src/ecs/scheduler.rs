@@ -0,0 +1,379 @@
+use crate::ecs::entity::Entity;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// The default energy an actor needs to act
+pub const DEFAULT_THRESHOLD: u32 = 100;
+
+#[derive(Clone, Debug)]
+struct ActorState {
+    speed: u32,
+    energy: i64,
+    updated: u64,
+    order: u64,
+    version: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ScheduledTurn {
+    time: u64,
+    speed: u32,
+    order: u64,
+    version: u64,
+    entity: Entity,
+}
+
+impl Ord for ScheduledTurn {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .time
+            .cmp(&self.time)
+            .then_with(|| self.speed.cmp(&other.speed))
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for ScheduledTurn {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Decides which actor acts next.
+///
+/// Each actor gains energy equal to its speed per tick and acts, when its energy reaches the threshold.
+/// Its action costs energy, so faster actors act more often & cheaper actions allow acting sooner.
+///
+/// Actors ready at the same tick act in order of decreasing speed and then in the order they were added.
+///
+/// ```
+///# use rust_roguelike_core::ecs::entity::EntityAllocator;
+///# use rust_roguelike_core::ecs::scheduler::TurnScheduler;
+/// let mut allocator = EntityAllocator::default();
+/// let player = allocator.create();
+/// let bat = allocator.create();
+/// let mut scheduler = TurnScheduler::default();
+/// scheduler.add_actor(player, 10);
+/// scheduler.add_actor(bat, 20);
+/// let mut turns = Vec::new();
+///
+/// for _ in 0..6 {
+///     let actor = scheduler.next_turn().unwrap();
+///     turns.push(actor);
+///     scheduler.end_turn(actor, 100);
+/// }
+///
+/// assert_eq!(turns, vec![bat, bat, player, bat, bat, player]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TurnScheduler {
+    threshold: u32,
+    time: u64,
+    actors: HashMap<Entity, ActorState>,
+    queue: BinaryHeap<ScheduledTurn>,
+    current: Option<Entity>,
+    counter: u64,
+}
+
+impl TurnScheduler {
+    /// Creates a new TurnScheduler without actors
+    pub fn new(threshold: u32) -> Self {
+        TurnScheduler {
+            threshold,
+            time: 0,
+            actors: HashMap::new(),
+            queue: BinaryHeap::new(),
+            current: None,
+            counter: 0,
+        }
+    }
+
+    /// Returns the number of ticks since the start
+    pub fn get_time(&self) -> u64 {
+        self.time
+    }
+
+    /// Returns the number of actors
+    pub fn get_actor_count(&self) -> usize {
+        self.actors.len()
+    }
+
+    /// Returns the current energy of an actor
+    pub fn get_energy(&self, entity: Entity) -> Option<i64> {
+        self.actors
+            .get(&entity)
+            .map(|actor| Self::energy_at(actor, self.time))
+    }
+
+    /// Returns the actor, whose turn has started but not ended yet
+    pub fn get_current(&self) -> Option<Entity> {
+        self.current
+    }
+
+    /// Adds an actor without energy. Actors with a speed of 0 never act.
+    ///
+    /// An actor, which was already added, keeps its energy and only changes its speed.
+    pub fn add_actor(&mut self, entity: Entity, speed: u32) {
+        if self.actors.contains_key(&entity) {
+            self.set_speed(entity, speed);
+            return;
+        }
+
+        self.counter += 1;
+        self.actors.insert(
+            entity,
+            ActorState {
+                speed,
+                energy: 0,
+                updated: self.time,
+                order: self.counter,
+                version: 0,
+            },
+        );
+        self.schedule(entity);
+    }
+
+    /// Removes an actor, for example because it died. Returns false, if it was unknown.
+    pub fn remove_actor(&mut self, entity: Entity) -> bool {
+        if self.current == Some(entity) {
+            self.current = None;
+        }
+
+        // Its queued turn is skipped later.
+        self.actors.remove(&entity).is_some()
+    }
+
+    /// Changes the speed of an actor, like a haste spell. Returns false, if it was unknown.
+    pub fn set_speed(&mut self, entity: Entity, speed: u32) -> bool {
+        let time = self.time;
+
+        match self.actors.get_mut(&entity) {
+            Some(actor) => {
+                actor.energy = Self::energy_at(actor, time);
+                actor.updated = time;
+                actor.speed = speed;
+            }
+            None => return false,
+        }
+
+        if self.current != Some(entity) {
+            self.schedule(entity);
+        }
+
+        true
+    }
+
+    /// Starts the turn of the next actor & advances the time to it.
+    ///
+    /// Returns the same actor again, until its turn was ended with [`TurnScheduler::end_turn`].
+    /// This lets the game wait for the player's input.
+    /// Returns `None`, if no actor can ever act.
+    pub fn next_turn(&mut self) -> Option<Entity> {
+        if self.current.is_some() {
+            return self.current;
+        }
+
+        while let Some(turn) = self.queue.pop() {
+            let is_valid = self
+                .actors
+                .get(&turn.entity)
+                .map(|actor| actor.version == turn.version)
+                .unwrap_or(false);
+
+            if is_valid {
+                self.time = self.time.max(turn.time);
+                self.current = Some(turn.entity);
+                return self.current;
+            }
+        }
+
+        None
+    }
+
+    /// Ends the turn of the current actor by paying the cost of its action.
+    ///
+    /// An actor with enough energy left acts again at the same tick.
+    /// Returns false, if it is not the actor's turn.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::entity::EntityAllocator;
+    ///# use rust_roguelike_core::ecs::scheduler::TurnScheduler;
+    /// let mut allocator = EntityAllocator::default();
+    /// let player = allocator.create();
+    /// let mut scheduler = TurnScheduler::new(100);
+    /// scheduler.add_actor(player, 10);
+    ///
+    /// assert_eq!(scheduler.next_turn(), Some(player));
+    /// assert_eq!(scheduler.get_time(), 10);
+    ///
+    /// // a slow action
+    /// assert!(scheduler.end_turn(player, 200));
+    /// assert_eq!(scheduler.next_turn(), Some(player));
+    /// assert_eq!(scheduler.get_time(), 30);
+    ///
+    /// // a quick action
+    /// assert!(scheduler.end_turn(player, 50));
+    /// assert_eq!(scheduler.next_turn(), Some(player));
+    /// assert_eq!(scheduler.get_time(), 35);
+    /// ```
+    pub fn end_turn(&mut self, entity: Entity, cost: u32) -> bool {
+        if self.current != Some(entity) {
+            return false;
+        }
+
+        self.current = None;
+        let time = self.time;
+
+        if let Some(actor) = self.actors.get_mut(&entity) {
+            actor.energy = Self::energy_at(actor, time) - cost as i64;
+            actor.updated = time;
+        }
+
+        self.schedule(entity);
+        true
+    }
+
+    fn energy_at(actor: &ActorState, time: u64) -> i64 {
+        actor.energy + (actor.speed as i64) * (time - actor.updated) as i64
+    }
+
+    fn schedule(&mut self, entity: Entity) {
+        let threshold = self.threshold as i64;
+        let time = self.time;
+
+        if let Some(actor) = self.actors.get_mut(&entity) {
+            // unique across all actors, so removed & re-added actors ignore their old turns
+            self.counter += 1;
+            actor.version = self.counter;
+
+            if actor.speed == 0 {
+                return;
+            }
+
+            let energy = Self::energy_at(actor, time);
+            let missing = (threshold - energy).max(0) as u64;
+            let speed = actor.speed as u64;
+
+            self.queue.push(ScheduledTurn {
+                time: time + missing.div_ceil(speed),
+                speed: actor.speed,
+                order: actor.order,
+                version: actor.version,
+                entity,
+            });
+        }
+    }
+}
+
+impl Default for TurnScheduler {
+    fn default() -> Self {
+        TurnScheduler::new(DEFAULT_THRESHOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::entity::EntityAllocator;
+
+    fn run(scheduler: &mut TurnScheduler, turns: usize, cost: u32) -> Vec<Entity> {
+        let mut result = Vec::new();
+
+        for _ in 0..turns {
+            let actor = scheduler.next_turn().unwrap();
+            result.push(actor);
+            assert!(scheduler.end_turn(actor, cost));
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_fast_actors_act_more_often() {
+        let mut allocator = EntityAllocator::default();
+        let slow = allocator.create();
+        let normal = allocator.create();
+        let fast = allocator.create();
+        let mut scheduler = TurnScheduler::default();
+        scheduler.add_actor(slow, 5);
+        scheduler.add_actor(normal, 10);
+        scheduler.add_actor(fast, 20);
+
+        let turns = run(&mut scheduler, 70, 100);
+        let count = |entity| turns.iter().filter(|e| **e == entity).count();
+
+        assert_eq!(count(slow), 10);
+        assert_eq!(count(normal), 20);
+        assert_eq!(count(fast), 40);
+        assert_eq!(scheduler.get_time(), 200);
+    }
+
+    #[test]
+    fn test_deterministic_tie_breaking() {
+        let mut allocator = EntityAllocator::default();
+        let a = allocator.create();
+        let b = allocator.create();
+        let c = allocator.create();
+        let mut scheduler = TurnScheduler::default();
+        scheduler.add_actor(a, 10);
+        scheduler.add_actor(b, 10);
+        scheduler.add_actor(c, 10);
+
+        assert_eq!(run(&mut scheduler, 6, 100), vec![a, b, c, a, b, c]);
+    }
+
+    #[test]
+    fn test_wait_for_current_actor() {
+        let mut allocator = EntityAllocator::default();
+        let player = allocator.create();
+        let orc = allocator.create();
+        let mut scheduler = TurnScheduler::default();
+        scheduler.add_actor(player, 20);
+        scheduler.add_actor(orc, 10);
+
+        assert_eq!(scheduler.next_turn(), Some(player));
+        assert_eq!(scheduler.next_turn(), Some(player));
+        assert!(!scheduler.end_turn(orc, 100));
+        assert_eq!(scheduler.get_current(), Some(player));
+        assert!(scheduler.end_turn(player, 100));
+        assert_eq!(scheduler.get_current(), None);
+    }
+
+    #[test]
+    fn test_remove_actor() {
+        let mut allocator = EntityAllocator::default();
+        let player = allocator.create();
+        let orc = allocator.create();
+        let mut scheduler = TurnScheduler::default();
+        scheduler.add_actor(player, 10);
+        scheduler.add_actor(orc, 10);
+
+        assert!(scheduler.remove_actor(orc));
+        assert!(!scheduler.remove_actor(orc));
+        assert_eq!(run(&mut scheduler, 3, 100), vec![player; 3]);
+        assert_eq!(scheduler.get_actor_count(), 1);
+    }
+
+    #[test]
+    fn test_change_speed() {
+        let mut allocator = EntityAllocator::default();
+        let player = allocator.create();
+        let statue = allocator.create();
+        let mut scheduler = TurnScheduler::default();
+        scheduler.add_actor(player, 10);
+        scheduler.add_actor(statue, 0);
+
+        assert_eq!(scheduler.next_turn(), Some(player));
+        assert!(scheduler.end_turn(player, 100));
+        assert!(scheduler.set_speed(player, 50));
+        assert_eq!(scheduler.next_turn(), Some(player));
+        assert_eq!(scheduler.get_time(), 12);
+        assert_eq!(scheduler.get_energy(player), Some(100));
+        assert_eq!(scheduler.get_energy(statue), Some(0));
+        assert!(scheduler.end_turn(player, 100));
+
+        scheduler.remove_actor(player);
+
+        assert_eq!(scheduler.next_turn(), None);
+    }
+}
@@ -0,0 +1,361 @@
+use crate::algorithm::pathfinding::profile::MovementProfile;
+use crate::ecs::entity::Entity;
+use crate::ecs::position::Position;
+use crate::ecs::World;
+use crate::math::distance::DistanceCalculator;
+use crate::math::rectangle::Rectangle;
+use crate::math::size2d::Size2d;
+use std::collections::HashMap;
+
+/// Finds the entities on the tiles of a 2d map.
+///
+/// A tile can contain multiple entities, which are kept in the order they entered it.
+#[derive(Clone, Debug, Default)]
+pub struct SpatialIndex {
+    size: Size2d,
+    tiles: HashMap<usize, Vec<Entity>>,
+    entities: HashMap<Entity, usize>,
+}
+
+impl SpatialIndex {
+    /// Creates a new empty SpatialIndex for a map of a specific size
+    pub fn new(size: Size2d) -> Self {
+        SpatialIndex {
+            size,
+            tiles: HashMap::new(),
+            entities: HashMap::new(),
+        }
+    }
+
+    /// Creates a new SpatialIndex from the [`Position`] components of a [`World`]
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::World;
+    ///# use rust_roguelike_core::ecs::position::Position;
+    ///# use rust_roguelike_core::ecs::spatial::SpatialIndex;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut world = World::default();
+    /// let player = world.create_entity();
+    /// let item = world.create_entity();
+    /// world.insert(player, Position::new(5));
+    /// world.insert(item, Position::new(5));
+    /// let index = SpatialIndex::from_world(&world, Size2d::new(4, 3));
+    ///
+    /// assert_eq!(index.get_entities_at(5), &[player, item]);
+    /// assert_eq!(index.get_tile(item), Some(5));
+    /// ```
+    pub fn from_world(world: &World, size: Size2d) -> Self {
+        let mut index = SpatialIndex::new(size);
+
+        for (entity, position) in world.query::<Position>() {
+            index.insert(entity, position.index);
+        }
+
+        index
+    }
+
+    pub fn get_size(&self) -> Size2d {
+        self.size
+    }
+
+    /// Adds an entity to a tile or moves it there, if it was already added.
+    /// Returns false, if the tile is outside the map.
+    pub fn insert(&mut self, entity: Entity, tile: usize) -> bool {
+        if tile >= self.size.get_tiles() {
+            return false;
+        }
+
+        self.remove(entity);
+        self.tiles.entry(tile).or_default().push(entity);
+        self.entities.insert(entity, tile);
+        true
+    }
+
+    /// Moves an entity to another tile. Returns false, if it was not added or the tile is outside the map.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::entity::EntityAllocator;
+    ///# use rust_roguelike_core::ecs::spatial::SpatialIndex;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut allocator = EntityAllocator::default();
+    /// let orc = allocator.create();
+    /// let mut index = SpatialIndex::new(Size2d::new(4, 3));
+    ///
+    /// assert!(!index.move_entity(orc, 3));
+    ///
+    /// index.insert(orc, 2);
+    ///
+    /// assert!(index.move_entity(orc, 3));
+    /// assert!(!index.move_entity(orc, 12));
+    /// assert!(!index.is_occupied(2));
+    /// assert!(index.is_occupied(3));
+    /// ```
+    pub fn move_entity(&mut self, entity: Entity, tile: usize) -> bool {
+        if !self.entities.contains_key(&entity) {
+            return false;
+        }
+
+        self.insert(entity, tile)
+    }
+
+    /// Removes an entity and returns its last tile
+    pub fn remove(&mut self, entity: Entity) -> Option<usize> {
+        let tile = self.entities.remove(&entity)?;
+
+        if let Some(entities) = self.tiles.get_mut(&tile) {
+            entities.retain(|e| *e != entity);
+
+            if entities.is_empty() {
+                self.tiles.remove(&tile);
+            }
+        }
+
+        Some(tile)
+    }
+
+    /// Returns the tile of an entity
+    pub fn get_tile(&self, entity: Entity) -> Option<usize> {
+        self.entities.get(&entity).cloned()
+    }
+
+    /// Returns the entities on a tile
+    pub fn get_entities_at(&self, tile: usize) -> &[Entity] {
+        self.tiles
+            .get(&tile)
+            .map(|entities| entities.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Does the tile contain any entity?
+    pub fn is_occupied(&self, tile: usize) -> bool {
+        self.tiles.contains_key(&tile)
+    }
+
+    /// Returns the entities inside the rectangle row by row
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::entity::EntityAllocator;
+    ///# use rust_roguelike_core::ecs::spatial::SpatialIndex;
+    ///# use rust_roguelike_core::math::rectangle::Rectangle;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut allocator = EntityAllocator::default();
+    /// let a = allocator.create();
+    /// let b = allocator.create();
+    /// let c = allocator.create();
+    /// let mut index = SpatialIndex::new(Size2d::new(4, 3));
+    /// index.insert(a, 10);
+    /// index.insert(b, 5);
+    /// index.insert(c, 0);
+    ///
+    /// assert_eq!(index.get_entities_in_rectangle(&Rectangle::new(1, 1, Size2d::new(3, 2))), vec![b, a]);
+    /// ```
+    pub fn get_entities_in_rectangle(&self, rectangle: &Rectangle) -> Vec<Entity> {
+        let end_x = rectangle.end_x().min(self.size.width());
+        let end_y = rectangle.end_y().min(self.size.height());
+        let mut entities = Vec::new();
+
+        for y in rectangle.y()..end_y {
+            for x in rectangle.x()..end_x {
+                entities.extend_from_slice(self.get_entities_at(self.size.to_index(x, y)));
+            }
+        }
+
+        entities
+    }
+
+    /// Returns the entities within a radius around a tile row by row
+    ///
+    /// ```
+    ///# use rust_roguelike_core::ecs::entity::EntityAllocator;
+    ///# use rust_roguelike_core::ecs::spatial::SpatialIndex;
+    ///# use rust_roguelike_core::math::distance::DistanceCalculator;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut allocator = EntityAllocator::default();
+    /// let a = allocator.create();
+    /// let b = allocator.create();
+    /// let mut index = SpatialIndex::new(Size2d::new(5, 5));
+    /// index.insert(a, 6);
+    /// index.insert(b, 18);
+    ///
+    /// assert_eq!(index.get_entities_in_radius(12, 1, DistanceCalculator::Chebyshev), vec![a, b]);
+    /// assert!(index.get_entities_in_radius(12, 1, DistanceCalculator::Manhattan).is_empty());
+    /// ```
+    pub fn get_entities_in_radius(
+        &self,
+        center: usize,
+        radius: u32,
+        calculator: DistanceCalculator,
+    ) -> Vec<Entity> {
+        if center >= self.size.get_tiles() {
+            return Vec::new();
+        }
+
+        let [center_x, center_y] = self.size.to_point(center);
+        let start_x = center_x.saturating_sub(radius);
+        let start_y = center_y.saturating_sub(radius);
+        let end_x = center_x
+            .saturating_add(radius)
+            .saturating_add(1)
+            .min(self.size.width());
+        let end_y = center_y
+            .saturating_add(radius)
+            .saturating_add(1)
+            .min(self.size.height());
+        let mut entities = Vec::new();
+
+        for y in start_y..end_y {
+            for x in start_x..end_x {
+                let distance =
+                    calculator.calculate_2d(center_x as i32, center_y as i32, x as i32, y as i32);
+
+                if distance <= radius {
+                    entities.extend_from_slice(self.get_entities_at(self.size.to_index(x, y)));
+                }
+            }
+        }
+
+        entities
+    }
+}
+
+/// A [`MovementProfile`] treating tiles occupied by entities as blocked.
+///
+/// The tiles of ignored entities stay free, like the tile of the moving entity or its target.
+///
+/// ```
+///# use rust_roguelike_core::algorithm::pathfinding::a_star::AStar;
+///# use rust_roguelike_core::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+///# use rust_roguelike_core::ecs::entity::EntityAllocator;
+///# use rust_roguelike_core::ecs::spatial::{BlockOccupied, SpatialIndex};
+///# use rust_roguelike_core::math::graph::map2d::Map2d;
+///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+///# use rust_roguelike_core::math::size2d::Size2d;
+/// let map = OccupancyMap::new(Size2d::new(4, 1), false);
+/// let mut allocator = EntityAllocator::default();
+/// let orc = allocator.create();
+/// let goblin = allocator.create();
+/// let mut index = SpatialIndex::new(map.get_size_2d());
+/// index.insert(orc, 0);
+/// index.insert(goblin, 3);
+/// let profile = BlockOccupied::new(&index, vec![orc, goblin]);
+///
+/// assert_eq!(AStar::default().find_with_profile(&map, &profile, 0, 3),
+///            PathfindingResult::Path { indices: vec![1, 2, 3], cost: 3 });
+///
+/// let profile = BlockOccupied::new(&index, vec![orc]);
+///
+/// assert_eq!(AStar::default().find_with_profile(&map, &profile, 0, 3),
+///            PathfindingResult::InvalidGoal);
+/// ```
+pub struct BlockOccupied<'a> {
+    index: &'a SpatialIndex,
+    ignored: Vec<Entity>,
+}
+
+impl<'a> BlockOccupied<'a> {
+    /// Creates a new BlockOccupied
+    pub fn new(index: &'a SpatialIndex, ignored: Vec<Entity>) -> Self {
+        BlockOccupied { index, ignored }
+    }
+}
+
+impl<'a, E> MovementProfile<E> for BlockOccupied<'a> {
+    fn is_valid(&self, index: usize, is_valid: bool) -> bool {
+        is_valid
+            && self
+                .index
+                .get_entities_at(index)
+                .iter()
+                .all(|entity| self.ignored.contains(entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::pathfinding::a_star::AStar;
+    use crate::algorithm::pathfinding::PathfindingAlgorithm;
+    use crate::algorithm::pathfinding::PathfindingResult::*;
+    use crate::ecs::entity::EntityAllocator;
+    use crate::math::graph::occupancy::OccupancyMap;
+
+    #[test]
+    fn test_multiple_entities_per_tile() {
+        let mut allocator = EntityAllocator::default();
+        let a = allocator.create();
+        let b = allocator.create();
+        let c = allocator.create();
+        let mut index = SpatialIndex::new(Size2d::new(3, 3));
+
+        assert!(index.insert(a, 4));
+        assert!(index.insert(b, 4));
+        assert!(index.insert(c, 4));
+        assert!(!index.insert(c, 9));
+        assert_eq!(index.get_entities_at(4), &[a, b, c]);
+
+        assert_eq!(index.remove(b), Some(4));
+        assert_eq!(index.remove(b), None);
+        assert_eq!(index.get_entities_at(4), &[a, c]);
+
+        assert!(index.insert(a, 5));
+        assert_eq!(index.get_entities_at(4), &[c]);
+        assert_eq!(index.get_entities_at(5), &[a]);
+
+        index.remove(c);
+
+        assert!(!index.is_occupied(4));
+        assert!(index.get_entities_at(4).is_empty());
+    }
+
+    #[test]
+    fn test_queries_are_clipped_to_map() {
+        let mut allocator = EntityAllocator::default();
+        let a = allocator.create();
+        let b = allocator.create();
+        let mut index = SpatialIndex::new(Size2d::new(3, 3));
+        index.insert(a, 0);
+        index.insert(b, 8);
+
+        assert_eq!(
+            index.get_entities_in_rectangle(&Rectangle::new(1, 1, Size2d::new(10, 10))),
+            vec![b]
+        );
+        assert_eq!(
+            index.get_entities_in_radius(0, 100, DistanceCalculator::Manhattan),
+            vec![a, b]
+        );
+        assert_eq!(
+            index.get_entities_in_radius(0, 3, DistanceCalculator::Manhattan),
+            vec![a]
+        );
+        assert!(index
+            .get_entities_in_radius(9, 100, DistanceCalculator::Manhattan)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_path_around_monster() {
+        // #####
+        // #...#
+        // #SMG#
+        // #####
+        let size = Size2d::new(5, 4);
+        let mut map = OccupancyMap::new(size, false);
+        map.add_border();
+        let mut allocator = EntityAllocator::default();
+        let player = allocator.create();
+        let monster = allocator.create();
+        let mut index = SpatialIndex::new(size);
+        index.insert(player, 11);
+        index.insert(monster, 12);
+        let profile = BlockOccupied::new(&index, vec![player]);
+
+        assert_eq!(
+            AStar::default().find_with_profile(&map, &profile, 11, 13),
+            Path {
+                indices: vec![6, 7, 8, 13],
+                cost: 4,
+            }
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use crate::ecs::entity::Entity;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Stores the components of one type for all entities
+pub trait Storage<T>: Default {
+    /// Adds a component to an entity and returns the replaced one
+    fn insert(&mut self, entity: Entity, component: T) -> Option<T>;
+
+    /// Removes the component of an entity
+    fn remove(&mut self, entity: Entity) -> Option<T>;
+
+    fn get(&self, entity: Entity) -> Option<&T>;
+
+    fn get_mut(&mut self, entity: Entity) -> Option<&mut T>;
+
+    /// Returns all entities with a component of this type sorted by index
+    fn get_entities(&self) -> Vec<Entity>;
+
+    /// Returns the number of components
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        self.get(entity).is_some()
+    }
+}
+
+/// Allows the [`World`](crate::ecs::World) to handle storages without knowing their component type
+pub trait AnyStorage: Any {
+    /// Removes the component of a destroyed entity
+    fn remove_entity(&mut self, entity: Entity);
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Stores components in a vector indexed by entity.
+///
+/// Fast for components most entities have, like positions.
+#[derive(Clone, Debug)]
+pub struct DenseStorage<T> {
+    components: Vec<Option<(Entity, T)>>,
+    count: usize,
+}
+
+impl<T> Default for DenseStorage<T> {
+    fn default() -> Self {
+        DenseStorage {
+            components: Vec::new(),
+            count: 0,
+        }
+    }
+}
+
+impl<T> Storage<T> for DenseStorage<T> {
+    fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
+        let index = entity.index();
+
+        if index >= self.components.len() {
+            self.components.resize_with(index + 1, || None);
+        }
+
+        let previous = self.components[index].replace((entity, component));
+
+        match previous {
+            Some((previous_entity, previous_component)) if previous_entity == entity => {
+                Some(previous_component)
+            }
+            Some(_) => None,
+            None => {
+                self.count += 1;
+                None
+            }
+        }
+    }
+
+    fn remove(&mut self, entity: Entity) -> Option<T> {
+        let slot = self.components.get_mut(entity.index())?;
+
+        match slot {
+            Some((e, _)) if *e == entity => {
+                self.count -= 1;
+                slot.take().map(|(_, component)| component)
+            }
+            _ => None,
+        }
+    }
+
+    fn get(&self, entity: Entity) -> Option<&T> {
+        match self.components.get(entity.index()) {
+            Some(Some((e, component))) if *e == entity => Some(component),
+            _ => None,
+        }
+    }
+
+    fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        match self.components.get_mut(entity.index()) {
+            Some(Some((e, component))) if *e == entity => Some(component),
+            _ => None,
+        }
+    }
+
+    fn get_entities(&self) -> Vec<Entity> {
+        self.components
+            .iter()
+            .filter_map(|slot| slot.as_ref().map(|(entity, _)| *entity))
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.count
+    }
+}
+
+impl<T: 'static> AnyStorage for DenseStorage<T> {
+    fn remove_entity(&mut self, entity: Entity) {
+        self.remove(entity);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Stores components in a hash map.
+///
+/// Saves memory for components few entities have, like a burning status.
+#[derive(Clone, Debug)]
+pub struct SparseStorage<T> {
+    components: HashMap<Entity, T>,
+}
+
+impl<T> Default for SparseStorage<T> {
+    fn default() -> Self {
+        SparseStorage {
+            components: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Storage<T> for SparseStorage<T> {
+    fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
+        self.components.insert(entity, component)
+    }
+
+    fn remove(&mut self, entity: Entity) -> Option<T> {
+        self.components.remove(&entity)
+    }
+
+    fn get(&self, entity: Entity) -> Option<&T> {
+        self.components.get(&entity)
+    }
+
+    fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.components.get_mut(&entity)
+    }
+
+    fn get_entities(&self) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = self.components.keys().cloned().collect();
+        entities.sort_by_key(|entity| entity.index());
+        entities
+    }
+
+    fn len(&self) -> usize {
+        self.components.len()
+    }
+}
+
+impl<T: 'static> AnyStorage for SparseStorage<T> {
+    fn remove_entity(&mut self, entity: Entity) {
+        self.remove(entity);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::entity::EntityAllocator;
+
+    fn test_storage<S: Storage<u32>>() {
+        let mut allocator = EntityAllocator::default();
+        let a = allocator.create();
+        let b = allocator.create();
+        let mut storage = S::default();
+
+        assert_eq!(storage.insert(b, 2), None);
+        assert_eq!(storage.insert(a, 1), None);
+        assert_eq!(storage.insert(a, 3), Some(1));
+        assert_eq!(storage.len(), 2);
+        assert_eq!(storage.get_entities(), vec![a, b]);
+
+        *storage.get_mut(b).unwrap() += 10;
+
+        assert_eq!(storage.get(b), Some(&12));
+        assert_eq!(storage.remove(a), Some(3));
+        assert_eq!(storage.remove(a), None);
+        assert!(!storage.contains(a));
+
+        allocator.destroy(b);
+        let c = allocator.create();
+
+        assert_eq!(storage.get(c), None);
+        assert_eq!(storage.remove(c), None);
+        assert_eq!(storage.insert(c, 5), None);
+        assert_eq!(storage.get(c), Some(&5));
+    }
+
+    #[test]
+    fn test_dense_storage() {
+        test_storage::<DenseStorage<u32>>();
+    }
+
+    #[test]
+    fn test_sparse_storage() {
+        test_storage::<SparseStorage<u32>>();
+    }
+}
@@ -0,0 +1,119 @@
+use crate::ecs::World;
+
+/// Changes the [`World`] each turn, like moving monsters or applying poison
+pub trait System {
+    fn run(&mut self, world: &mut World);
+}
+
+impl<F: FnMut(&mut World)> System for F {
+    fn run(&mut self, world: &mut World) {
+        self(world)
+    }
+}
+
+/// Runs systems in the order they were added
+///
+/// ```
+///# use rust_roguelike_core::ecs::{Component, World};
+///# use rust_roguelike_core::ecs::storage::DenseStorage;
+///# use rust_roguelike_core::ecs::system::SystemRunner;
+/// struct Health(u32);
+///
+/// impl Component for Health {
+///     type Storage = DenseStorage<Self>;
+/// }
+///
+/// let mut world = World::default();
+/// let orc = world.create_entity();
+/// world.insert(orc, Health(10));
+///
+/// let mut runner = SystemRunner::default();
+/// runner.add(|world: &mut World| {
+///     for entity in world.query_entities::<Health>() {
+///         world.get_mut::<Health>(entity).unwrap().0 *= 2;
+///     }
+/// });
+/// runner.add(|world: &mut World| {
+///     for entity in world.query_entities::<Health>() {
+///         world.get_mut::<Health>(entity).unwrap().0 -= 1;
+///     }
+/// });
+/// runner.run(&mut world);
+///
+/// assert_eq!(world.get::<Health>(orc).unwrap().0, 19);
+/// ```
+#[derive(Default)]
+pub struct SystemRunner {
+    systems: Vec<Box<dyn System>>,
+}
+
+impl SystemRunner {
+    /// Adds a system after all previous ones
+    pub fn add<S: System + 'static>(&mut self, system: S) {
+        self.systems.push(Box::new(system));
+    }
+
+    /// Returns the number of systems
+    pub fn get_count(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Runs each system once
+    pub fn run(&mut self, world: &mut World) {
+        for system in self.systems.iter_mut() {
+            system.run(world);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::storage::DenseStorage;
+    use crate::ecs::Component;
+
+    struct Counter(u32);
+
+    impl Component for Counter {
+        type Storage = DenseStorage<Self>;
+    }
+
+    struct Spawner {
+        remaining: u32,
+    }
+
+    impl System for Spawner {
+        fn run(&mut self, world: &mut World) {
+            if self.remaining > 0 {
+                self.remaining -= 1;
+                let entity = world.create_entity();
+                world.insert(entity, Counter(0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_systems_keep_state() {
+        let mut world = World::default();
+        let mut runner = SystemRunner::default();
+        runner.add(Spawner { remaining: 2 });
+        runner.add(|world: &mut World| {
+            for entity in world.query_entities::<Counter>() {
+                world.get_mut::<Counter>(entity).unwrap().0 += 1;
+            }
+        });
+
+        for _ in 0..3 {
+            runner.run(&mut world);
+        }
+
+        let counters: Vec<u32> = world
+            .query::<Counter>()
+            .into_iter()
+            .map(|(_, counter)| counter.0)
+            .collect();
+
+        assert_eq!(runner.get_count(), 2);
+        assert_eq!(counters, vec![3, 2]);
+    }
+}
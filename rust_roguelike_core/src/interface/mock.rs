@@ -0,0 +1,118 @@
+use crate::interface::rendering::*;
+use crate::math::color::Color;
+use crate::math::size2d::Size2d;
+
+/// Records the rendered text & rectangles, so tests can check them without a window
+#[derive(Default)]
+pub struct MockRenderer {
+    pub size: Size2d,
+    pub texts: Vec<([f32; 2], String, Color)>,
+    pub rectangles: Vec<([f32; 2], [f32; 2], Color)>,
+}
+
+impl MockRenderer {
+    pub fn new(size: Size2d) -> Self {
+        MockRenderer {
+            size,
+            ..MockRenderer::default()
+        }
+    }
+
+    /// Returns the rendered text at a position
+    pub fn get_text(&self, x: f32, y: f32) -> Option<&str> {
+        self.texts
+            .iter()
+            .find(|(position, _, _)| *position == [x, y])
+            .map(|(_, text, _)| text.as_str())
+    }
+
+    /// Returns the lines of a rectangle from top to bottom
+    pub fn get_lines(&self, x: u32, y: u32, width: u32, height: u32) -> Vec<String> {
+        let mut lines = vec![vec![' '; width as usize]; height as usize];
+
+        for ([tx, ty], text, _) in &self.texts {
+            let row = (y + height) as i32 - 1 - *ty as i32;
+
+            if row < 0 || row >= height as i32 {
+                continue;
+            }
+
+            for (i, c) in text.chars().enumerate() {
+                let column = *tx as i32 - x as i32 + i as i32;
+
+                if column >= 0 && column < width as i32 {
+                    lines[row as usize][column as usize] = c;
+                }
+            }
+        }
+
+        lines
+            .into_iter()
+            .map(|line| line.into_iter().collect::<String>().trim_end().to_string())
+            .collect()
+    }
+}
+
+impl Renderer for MockRenderer {
+    fn get_size(&self) -> Size2d {
+        self.size
+    }
+
+    fn start(&mut self, _color: Color) {}
+
+    fn finish(&mut self) {}
+
+    fn take_screenshot(&self, _filename: &str) {}
+
+    fn load_texture(&mut self, _filename: &str) -> TextureId {
+        0
+    }
+
+    fn get_color_renderer(&mut self) -> &mut dyn ColorRenderer {
+        self
+    }
+
+    fn get_texture_renderer(&mut self, _id: TextureId) -> &mut dyn TextureRenderer {
+        self
+    }
+
+    fn get_ascii_renderer(&mut self, _id: TextureId) -> &mut dyn AsciiRenderer {
+        self
+    }
+}
+
+impl ColorRenderer for MockRenderer {
+    fn render_triangle(&mut self, _a: [f32; 2], _b: [f32; 2], _c: [f32; 2], _color: Color) {}
+
+    fn render_rectangle(&mut self, position: [f32; 2], size: [f32; 2], color: Color) {
+        self.rectangles.push((position, size, color));
+    }
+}
+
+impl TextureRenderer for MockRenderer {
+    fn render_rectangle(
+        &mut self,
+        position: [f32; 2],
+        size: [f32; 2],
+        _tc: [f32; 2],
+        _tc_size: [f32; 2],
+        color: Color,
+    ) {
+        self.rectangles.push((position, size, color));
+    }
+}
+
+impl AsciiRenderer for MockRenderer {
+    fn render_text(&mut self, position: [f32; 2], _size: [f32; 2], string: &str, color: Color) {
+        self.texts.push((position, string.to_string(), color));
+    }
+
+    fn render_char(&mut self, position: [f32; 2], _size: [f32; 2], c: char, color: Color) {
+        self.texts.push((position, c.to_string(), color));
+    }
+
+    fn render_u8(&mut self, position: [f32; 2], _size: [f32; 2], ascii: u8, color: Color) {
+        self.texts
+            .push((position, (ascii as char).to_string(), color));
+    }
+}
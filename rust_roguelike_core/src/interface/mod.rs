@@ -1,5 +1,8 @@
 pub mod input;
+#[cfg(test)]
+pub(crate) mod mock;
 pub mod rendering;
+pub mod scene;
 
 use input::{KeyCode, MouseButton};
 use rendering::Renderer;
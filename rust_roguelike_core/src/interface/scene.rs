@@ -0,0 +1,351 @@
+use crate::interface::input::{KeyCode, MouseButton};
+use crate::interface::rendering::Renderer;
+use crate::interface::App;
+
+/// Tells the [`SceneManager`] how to change its stack of scenes
+pub enum Transition {
+    /// Stays in the current scene
+    None,
+    /// Adds a new scene on top of the current one, e.g. an inventory over the game
+    Push(Box<dyn Scene>),
+    /// Removes the current scene and returns to the one beneath
+    Pop,
+    /// Swaps the current scene with a new one, e.g. the main menu with the game
+    Replace(Box<dyn Scene>),
+}
+
+/// A screen of an application like a main menu, the game or an inventory.
+pub trait Scene {
+    /// Initializes the scene before it is updated or rendered for the first time.
+    fn init(&mut self, _renderer: &mut dyn Renderer) {}
+
+    /// Updates the scene once per frame before rendering.
+    fn update(&mut self) -> Transition {
+        Transition::None
+    }
+
+    /// Renders the scene.
+    fn render(&mut self, renderer: &mut dyn Renderer);
+
+    /// Handles keyboard input
+    fn on_key_released(&mut self, _key: KeyCode) -> Transition {
+        Transition::None
+    }
+
+    /// Handles mouse input
+    fn on_button_released(&mut self, _button: MouseButton, _index: usize) -> Transition {
+        Transition::None
+    }
+
+    /// Returns true, if the scene beneath should be rendered first, e.g. for a popup
+    fn is_transparent(&self) -> bool {
+        false
+    }
+}
+
+struct Entry {
+    scene: Box<dyn Scene>,
+    is_initialized: bool,
+}
+
+/// An [`App`] with a stack of scenes.
+///
+/// Only the top scene gets updated and receives input,
+/// but transparent scenes also render the scenes beneath them.
+///
+/// ```
+///# use rust_roguelike_core::interface::input::KeyCode;
+///# use rust_roguelike_core::interface::rendering::Renderer;
+///# use rust_roguelike_core::interface::scene::{Scene, SceneManager, Transition};
+/// struct Game;
+///
+/// impl Scene for Game {
+///     fn render(&mut self, _renderer: &mut dyn Renderer) {}
+///
+///     fn on_key_released(&mut self, key: KeyCode) -> Transition {
+///         match key {
+///             KeyCode::I => Transition::Push(Box::new(Inventory)),
+///             _ => Transition::None,
+///         }
+///     }
+/// }
+///
+/// struct Inventory;
+///
+/// impl Scene for Inventory {
+///     fn render(&mut self, _renderer: &mut dyn Renderer) {}
+///
+///     fn on_key_released(&mut self, _key: KeyCode) -> Transition {
+///         Transition::Pop
+///     }
+///
+///     fn is_transparent(&self) -> bool {
+///         true
+///     }
+/// }
+///
+/// let mut manager = SceneManager::new(Box::new(Game));
+///
+/// manager.handle_key(KeyCode::I);
+/// assert_eq!(manager.get_scene_count(), 2);
+///
+/// manager.handle_key(KeyCode::Escape);
+/// assert_eq!(manager.get_scene_count(), 1);
+/// ```
+pub struct SceneManager {
+    scenes: Vec<Entry>,
+}
+
+impl SceneManager {
+    /// Creates a new SceneManager with an initial scene
+    pub fn new(scene: Box<dyn Scene>) -> Self {
+        let mut manager = SceneManager { scenes: Vec::new() };
+        manager.push(scene);
+        manager
+    }
+
+    /// Returns the number of scenes on the stack
+    pub fn get_scene_count(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Returns true, if there are no scenes left, e.g. after the last one was popped
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// Adds a scene on top
+    pub fn push(&mut self, scene: Box<dyn Scene>) {
+        self.scenes.push(Entry {
+            scene,
+            is_initialized: false,
+        });
+    }
+
+    /// Removes the top scene
+    pub fn pop(&mut self) -> Option<Box<dyn Scene>> {
+        self.scenes.pop().map(|entry| entry.scene)
+    }
+
+    /// Replaces the top scene
+    pub fn replace(&mut self, scene: Box<dyn Scene>) -> Option<Box<dyn Scene>> {
+        let old = self.pop();
+        self.push(scene);
+        old
+    }
+
+    /// Applies a transition to the stack of scenes
+    pub fn apply(&mut self, transition: Transition) {
+        match transition {
+            Transition::None => {}
+            Transition::Push(scene) => self.push(scene),
+            Transition::Pop => {
+                self.pop();
+            }
+            Transition::Replace(scene) => {
+                self.replace(scene);
+            }
+        }
+    }
+
+    /// Forwards keyboard input to the top scene
+    pub fn handle_key(&mut self, key: KeyCode) {
+        if let Some(entry) = self.scenes.last_mut() {
+            let transition = entry.scene.on_key_released(key);
+            self.apply(transition);
+        }
+    }
+
+    /// Forwards mouse input to the top scene
+    pub fn handle_button(&mut self, button: MouseButton, index: usize) {
+        if let Some(entry) = self.scenes.last_mut() {
+            let transition = entry.scene.on_button_released(button, index);
+            self.apply(transition);
+        }
+    }
+
+    fn init_top(&mut self, renderer: &mut dyn Renderer) {
+        if let Some(entry) = self.scenes.last_mut() {
+            if !entry.is_initialized {
+                entry.scene.init(renderer);
+                entry.is_initialized = true;
+            }
+        }
+    }
+
+    fn get_first_visible(&self) -> usize {
+        self.scenes
+            .iter()
+            .rposition(|entry| !entry.scene.is_transparent())
+            .unwrap_or(0)
+    }
+}
+
+impl App for SceneManager {
+    fn init(&mut self, renderer: &mut dyn Renderer) {
+        self.init_top(renderer);
+    }
+
+    fn render(&mut self, renderer: &mut dyn Renderer) {
+        self.init_top(renderer);
+
+        if let Some(entry) = self.scenes.last_mut() {
+            let transition = entry.scene.update();
+            self.apply(transition);
+            self.init_top(renderer);
+        }
+
+        let first = self.get_first_visible();
+
+        for entry in &mut self.scenes[first..] {
+            if !entry.is_initialized {
+                entry.scene.init(renderer);
+                entry.is_initialized = true;
+            }
+
+            entry.scene.render(renderer);
+        }
+    }
+
+    fn on_key_released(&mut self, key: KeyCode) {
+        self.handle_key(key);
+    }
+
+    fn on_button_released(&mut self, button: MouseButton, index: usize) {
+        self.handle_button(button, index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::mock::MockRenderer;
+    use crate::math::color::WHITE;
+    use crate::math::size2d::Size2d;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Events = Rc<RefCell<Vec<String>>>;
+
+    struct TestScene {
+        name: &'static str,
+        events: Events,
+        is_transparent: bool,
+        on_update: Option<Transition>,
+    }
+
+    impl TestScene {
+        fn create(name: &'static str, events: &Events, is_transparent: bool) -> Box<dyn Scene> {
+            Box::new(TestScene {
+                name,
+                events: events.clone(),
+                is_transparent,
+                on_update: None,
+            })
+        }
+
+        fn log(&self, event: &str) {
+            self.events
+                .borrow_mut()
+                .push(format!("{} {}", self.name, event));
+        }
+    }
+
+    impl Scene for TestScene {
+        fn init(&mut self, _renderer: &mut dyn Renderer) {
+            self.log("init");
+        }
+
+        fn update(&mut self) -> Transition {
+            self.log("update");
+            self.on_update.take().unwrap_or(Transition::None)
+        }
+
+        fn render(&mut self, renderer: &mut dyn Renderer) {
+            self.log("render");
+            renderer
+                .get_ascii_renderer(0)
+                .render_text([0.0, 0.0], [1.0, 1.0], self.name, WHITE);
+        }
+
+        fn on_key_released(&mut self, key: KeyCode) -> Transition {
+            self.log(&format!("{:?}", key));
+            Transition::Pop
+        }
+
+        fn is_transparent(&self) -> bool {
+            self.is_transparent
+        }
+    }
+
+    fn take(events: &Events) -> Vec<String> {
+        events.borrow_mut().drain(..).collect()
+    }
+
+    #[test]
+    fn test_transparent_overlays() {
+        let events = Events::default();
+        let mut manager = SceneManager::new(TestScene::create("game", &events, false));
+        let mut renderer = MockRenderer::new(Size2d::new(10, 10));
+        manager.init(&mut renderer);
+        manager.push(TestScene::create("inventory", &events, true));
+        manager.push(TestScene::create("popup", &events, true));
+        manager.render(&mut renderer);
+
+        assert_eq!(
+            take(&events),
+            vec![
+                "game init",
+                "popup init",
+                "popup update",
+                "game render",
+                "inventory init",
+                "inventory render",
+                "popup render",
+            ]
+        );
+
+        manager.replace(TestScene::create("menu", &events, false));
+        manager.render(&mut renderer);
+
+        assert_eq!(
+            take(&events),
+            vec!["menu init", "menu update", "menu render"]
+        );
+    }
+
+    #[test]
+    fn test_input_goes_to_top_scene() {
+        let events = Events::default();
+        let mut manager = SceneManager::new(TestScene::create("game", &events, false));
+        manager.push(TestScene::create("inventory", &events, false));
+
+        manager.on_key_released(KeyCode::A);
+        manager.on_key_released(KeyCode::B);
+        manager.on_key_released(KeyCode::C);
+
+        assert_eq!(take(&events), vec!["inventory A", "game B"]);
+        assert!(manager.is_empty());
+    }
+
+    #[test]
+    fn test_transition_from_update() {
+        let events = Events::default();
+        let mut manager = SceneManager::new(Box::new(TestScene {
+            name: "intro",
+            events: events.clone(),
+            is_transparent: false,
+            on_update: Some(Transition::Replace(TestScene::create(
+                "game", &events, false,
+            ))),
+        }));
+        let mut renderer = MockRenderer::new(Size2d::new(10, 10));
+        manager.render(&mut renderer);
+
+        assert_eq!(
+            take(&events),
+            vec!["intro init", "intro update", "game init", "game render"]
+        );
+        assert_eq!(renderer.get_text(0.0, 0.0), Some("game"));
+    }
+}
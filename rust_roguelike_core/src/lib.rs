@@ -1,4 +1,6 @@
 pub mod algorithm;
+pub mod ecs;
 pub mod interface;
 pub mod math;
 pub mod rendering;
+pub mod ui;
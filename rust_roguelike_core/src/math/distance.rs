@@ -1,18 +1,17 @@
+use crate::math::hex::{calculate_hex_distance, offset_to_axial};
+
 /// Different ways to calculate the distance between 2 points.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 pub enum DistanceCalculator {
     /// See [Wikipedia](https://en.wikipedia.org/wiki/Chebyshev_distance)
     Chebyshev,
+    /// The number of steps between the offset coordinates of a [`HexMap`](crate::math::graph::hex::HexMap)
+    Hex,
     /// See [Wikipedia](https://en.wikipedia.org/wiki/Manhattan_distance)
+    #[default]
     Manhattan,
 }
 
-impl Default for DistanceCalculator {
-    fn default() -> Self {
-        DistanceCalculator::Manhattan
-    }
-}
-
 impl DistanceCalculator {
     /// Returns the distance between 2 points in 2d
     /// ```
@@ -21,11 +20,18 @@ impl DistanceCalculator {
     /// assert_eq!(DistanceCalculator::Chebyshev.calculate_2d(14, 29, 10, 20), 9);
     /// assert_eq!(DistanceCalculator::Manhattan.calculate_2d(10, 20, 14, 29), 13);
     /// assert_eq!(DistanceCalculator::Manhattan.calculate_2d(14, 29, 10, 20), 13);
+    /// assert_eq!(DistanceCalculator::Hex.calculate_2d(0, 0, 0, 1), 1);
+    /// assert_eq!(DistanceCalculator::Hex.calculate_2d(1, 1, 0, 0), 2);
+    /// assert_eq!(DistanceCalculator::Hex.calculate_2d(10, 20, 14, 29), 9);
     /// ```
     pub fn calculate_2d(&self, from_x: i32, from_y: i32, to_x: i32, to_y: i32) -> u32 {
         (match self {
             DistanceCalculator::Chebyshev => (to_x - from_x).abs().max((to_y - from_y).abs()),
             DistanceCalculator::Manhattan => (to_x - from_x).abs() + (to_y - from_y).abs(),
+            DistanceCalculator::Hex => calculate_hex_distance(
+                offset_to_axial([from_x, from_y]),
+                offset_to_axial([to_x, to_y]),
+            ) as i32,
         }) as u32
     }
 }
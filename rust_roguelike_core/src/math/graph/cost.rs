@@ -0,0 +1,209 @@
+use crate::algorithm::pathfinding::CostCalculator;
+use crate::math::distance::DistanceCalculator;
+use crate::math::graph::map2d::{Direction2d, Map2d};
+use crate::math::graph::occupancy::OccupancyMap;
+use crate::math::graph::{Graph, Neighbor};
+use crate::math::size2d::Size2d;
+
+/// The cost of tiles that can not be entered
+pub const IMPASSABLE: u32 = u32::MAX;
+
+/// A 2d map storing the cost of entering each tile
+///
+/// The costs should be at least 1, because the heuristics of the pathfinding assume it.
+#[derive(Clone, Debug, Default)]
+pub struct CostMap {
+    costs: Vec<u32>,
+    size: Size2d,
+}
+
+impl CostMap {
+    /// Creates a new CostMap, where all tiles have the same cost
+    pub fn new(size: Size2d, default: u32) -> Self {
+        CostMap {
+            costs: vec![default; size.get_tiles()],
+            size,
+        }
+    }
+
+    /// Creates a new CostMap from an OccupancyMap and a terrain type per tile.
+    ///
+    /// Occupied tiles are impassable and free tiles get the cost of their terrain from the table.
+    /// Terrain types without an entry in the table are impassable too.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::cost::{CostMap, IMPASSABLE};
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut occupancy_map = OccupancyMap::new(Size2d::new(4, 1), false);
+    /// occupancy_map.set_node(0, true);
+    /// let terrain = [0, 0, 1, 2];
+    /// let table = [1, 5];
+    ///
+    /// let map = CostMap::from_terrain(&occupancy_map, &terrain, &table);
+    ///
+    /// assert_eq!(map.get_cost(0), IMPASSABLE);
+    /// assert_eq!(map.get_cost(1), 1);
+    /// assert_eq!(map.get_cost(2), 5);
+    /// assert_eq!(map.get_cost(3), IMPASSABLE);
+    /// ```
+    pub fn from_terrain(occupancy_map: &OccupancyMap, terrain: &[usize], table: &[u32]) -> Self {
+        let size = occupancy_map.get_size_2d();
+        let costs = occupancy_map
+            .is_occupied
+            .iter()
+            .enumerate()
+            .map(|(index, is_occupied)| {
+                if *is_occupied {
+                    return IMPASSABLE;
+                }
+
+                terrain
+                    .get(index)
+                    .and_then(|terrain| table.get(*terrain))
+                    .cloned()
+                    .unwrap_or(IMPASSABLE)
+            })
+            .collect();
+
+        CostMap { costs, size }
+    }
+
+    /// Returns the cost of entering a tile or [`IMPASSABLE`] for indices outside the map
+    pub fn get_cost(&self, index: usize) -> u32 {
+        *self.costs.get(index).unwrap_or(&IMPASSABLE)
+    }
+
+    pub fn set_cost(&mut self, index: usize, cost: u32) {
+        self.costs[index] = cost;
+    }
+
+    fn add_neighbor(
+        &self,
+        neighbors: &mut Vec<Neighbor<Direction2d>>,
+        point: [u32; 2],
+        dir: Direction2d,
+        dx: i32,
+        dy: i32,
+    ) {
+        let x = point[0] as i32 + dx;
+        let y = point[1] as i32 + dy;
+
+        if x < 0 || y < 0 || x >= self.size.width() as i32 || y >= self.size.height() as i32 {
+            return;
+        }
+
+        let index = self.size.to_index(x as u32, y as u32);
+        neighbors.push(Neighbor { index, edge: dir });
+    }
+}
+
+impl CostCalculator<Direction2d> for CostMap {
+    fn is_valid(&self, index: usize) -> bool {
+        self.get_cost(index) != IMPASSABLE
+    }
+
+    /// Returns the cost of entering the neighbor
+    ///
+    /// ```
+    ///# use rust_roguelike_core::algorithm::pathfinding::CostCalculator;
+    ///# use rust_roguelike_core::math::graph::cost::CostMap;
+    ///# use rust_roguelike_core::math::graph::map2d::Direction2d;
+    ///# use rust_roguelike_core::math::graph::Neighbor;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut map = CostMap::new(Size2d::new(2, 1), 1);
+    /// map.set_cost(1, 7);
+    ///
+    /// assert_eq!(map.calculate_cost(0, &Neighbor { index: 1, edge: Direction2d::EAST }), 7);
+    /// assert_eq!(map.calculate_cost(1, &Neighbor { index: 0, edge: Direction2d::WEST }), 1);
+    /// ```
+    fn calculate_cost(&self, _index: usize, neighbor: &Neighbor<Direction2d>) -> u32 {
+        self.get_cost(neighbor.index)
+    }
+}
+
+impl Graph<u32, Direction2d> for CostMap {
+    fn get_size(&self) -> usize {
+        self.size.get_tiles()
+    }
+
+    fn get_node(&self, index: usize) -> Option<&u32> {
+        self.costs.get(index)
+    }
+
+    fn get_neighbors(&self, index: usize) -> Vec<Neighbor<Direction2d>> {
+        self.get_all_neighbors(index)
+            .into_iter()
+            .filter(|neighbor| self.is_valid(neighbor.index))
+            .collect()
+    }
+
+    fn get_all_neighbors(&self, index: usize) -> Vec<Neighbor<Direction2d>> {
+        let point = self.size.to_point(index);
+        let mut neighbors = Vec::new();
+
+        self.add_neighbor(&mut neighbors, point, Direction2d::NORTH, 0, 1);
+        self.add_neighbor(&mut neighbors, point, Direction2d::EAST, 1, 0);
+        self.add_neighbor(&mut neighbors, point, Direction2d::SOUTH, 0, -1);
+        self.add_neighbor(&mut neighbors, point, Direction2d::WEST, -1, 0);
+
+        neighbors
+    }
+
+    fn get_distance(&self, calculator: DistanceCalculator, from: usize, to: usize) -> u32 {
+        let [from_x, from_y] = self.size.to_point(from);
+        let [to_x, to_y] = self.size.to_point(to);
+        calculator.calculate_2d(from_x as i32, from_y as i32, to_x as i32, to_y as i32)
+    }
+}
+
+impl Map2d<u32, Direction2d> for CostMap {
+    fn get_size_2d(&self) -> Size2d {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::pathfinding::a_star::AStar;
+    use crate::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+
+    #[test]
+    fn test_neighbors_at_edge() {
+        let map = CostMap::new(Size2d::new(3, 3), 1);
+        let neighbors: Vec<usize> = map.get_neighbors(2).iter().map(|n| n.index).collect();
+
+        assert_eq!(neighbors, vec![5, 1]);
+    }
+
+    #[test]
+    fn test_impassable_neighbors() {
+        let mut map = CostMap::new(Size2d::new(3, 3), 1);
+        map.set_cost(1, IMPASSABLE);
+        map.set_cost(3, IMPASSABLE);
+
+        assert!(map.get_neighbors(0).is_empty());
+        assert_eq!(map.get_all_neighbors(0).len(), 2);
+    }
+
+    #[test]
+    fn test_avoid_swamp() {
+        // .....
+        // .~~~.
+        // S~~~G
+        let mut map = CostMap::new(Size2d::new(5, 3), 1);
+        for index in &[1, 2, 3, 6, 7, 8] {
+            map.set_cost(*index, 10);
+        }
+        let algorithm = AStar::default();
+
+        assert_eq!(
+            algorithm.find(&map, 0, 4),
+            PathfindingResult::Path {
+                indices: vec![5, 10, 11, 12, 13, 14, 9, 4],
+                cost: 8,
+            }
+        );
+    }
+}
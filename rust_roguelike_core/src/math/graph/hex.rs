@@ -0,0 +1,342 @@
+//! Maps of hexagons, which work with the graph algorithms like pathfinding & flood fill.
+//!
+//! Field of view is out of scope, because the crate has no field of view for square maps either.
+
+use crate::algorithm::pathfinding::CostCalculator;
+use crate::math::distance::DistanceCalculator;
+use crate::math::graph::map2d::Map2d;
+use crate::math::graph::occupancy::OccupancyMap;
+use crate::math::graph::{Graph, Neighbor};
+use crate::math::hex::{axial_to_offset, offset_to_axial};
+use crate::math::size2d::Size2d;
+
+/// The vertical distance between the centers of 2 rows of hexagons with a width of 1
+pub const HEX_ROW_HEIGHT: f32 = 0.75;
+
+/// The direction between neighbors in a [`HexMap`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HexDirection {
+    EAST,
+    NORTHEAST,
+    NORTHWEST,
+    WEST,
+    SOUTHWEST,
+    SOUTHEAST,
+}
+
+/// All directions counter-clockwise, starting with [`HexDirection::EAST`]
+pub const HEX_DIRECTIONS: [HexDirection; 6] = [
+    HexDirection::EAST,
+    HexDirection::NORTHEAST,
+    HexDirection::NORTHWEST,
+    HexDirection::WEST,
+    HexDirection::SOUTHWEST,
+    HexDirection::SOUTHEAST,
+];
+
+impl HexDirection {
+    /// Returns the change of the axial coordinates `[q, r]`
+    pub fn get_axial_offset(&self) -> [i32; 2] {
+        match self {
+            HexDirection::EAST => [1, 0],
+            HexDirection::NORTHEAST => [0, 1],
+            HexDirection::NORTHWEST => [-1, 1],
+            HexDirection::WEST => [-1, 0],
+            HexDirection::SOUTHWEST => [0, -1],
+            HexDirection::SOUTHEAST => [1, -1],
+        }
+    }
+}
+
+/// A 2d map of pointy-topped hexagons, where each one is either occupied or free.
+///
+/// The hexagons are stored in rows like a square grid. The y-axis points north
+/// and the odd rows are shifted half a hexagon to the east.
+/// Use [`HexMap::from_occupancy`] to reuse the square maps of the generators
+/// and [`DistanceCalculator::Hex`] to find the cheapest paths with [`AStar`](crate::algorithm::pathfinding::a_star::AStar).
+#[derive(Clone, Debug, Default)]
+pub struct HexMap {
+    pub is_occupied: Vec<bool>,
+    size: Size2d,
+}
+
+impl HexMap {
+    pub fn new(size: Size2d, default: bool) -> Self {
+        HexMap {
+            is_occupied: vec![default; size.get_tiles()],
+            size,
+        }
+    }
+
+    /// Creates a HexMap with the same occupied tiles
+    pub fn from_occupancy(map: &OccupancyMap) -> Self {
+        HexMap {
+            is_occupied: map.is_occupied.clone(),
+            size: map.get_size_2d(),
+        }
+    }
+
+    /// Occupies the hexagons at the edge of the map
+    pub fn add_border(&mut self) {
+        let width = self.size.width();
+        let height = self.size.height();
+
+        for index in 0..self.size.get_tiles() {
+            let [x, y] = self.size.to_point(index);
+
+            if x == 0 || y == 0 || x + 1 == width || y + 1 == height {
+                self.is_occupied[index] = true;
+            }
+        }
+    }
+
+    pub fn set_node(&mut self, index: usize, value: bool) {
+        self.is_occupied[index] = value;
+    }
+
+    /// Returns the axial coordinates `[q, r]` of a hexagon
+    pub fn to_axial(&self, index: usize) -> [i32; 2] {
+        let [x, y] = self.size.to_point(index);
+        offset_to_axial([x as i32, y as i32])
+    }
+
+    /// Returns the index of the hexagon at the axial coordinates or `None` outside the map
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::hex::HexMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = HexMap::new(Size2d::new(4, 3), false);
+    ///
+    /// assert_eq!(map.to_axial(9), [0, 2]);
+    /// assert_eq!(map.from_axial([0, 2]), Some(9));
+    /// assert_eq!(map.from_axial([-2, 2]), None);
+    /// ```
+    pub fn from_axial(&self, axial: [i32; 2]) -> Option<usize> {
+        let [x, y] = axial_to_offset(axial);
+
+        if x >= 0 && y >= 0 && x < self.size.width() as i32 && y < self.size.height() as i32 {
+            Some(self.size.to_index(x as u32, y as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the neighboring hexagon in a direction or `None` outside the map
+    pub fn get_neighbor(&self, index: usize, direction: HexDirection) -> Option<usize> {
+        let [q, r] = self.to_axial(index);
+        let [dq, dr] = direction.get_axial_offset();
+        self.from_axial([q + dq, r + dr])
+    }
+
+    /// Returns the center of a hexagon in tiles, if each hexagon is 1 tile wide
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::hex::HexMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = HexMap::new(Size2d::new(4, 3), false);
+    ///
+    /// assert_eq!(map.get_center(0), [0.5, 0.5]);
+    /// assert_eq!(map.get_center(4), [1.0, 1.25]);
+    /// ```
+    pub fn get_center(&self, index: usize) -> [f32; 2] {
+        let [x, y] = self.size.to_point(index);
+        let shift = (y % 2) as f32 * 0.5;
+
+        [x as f32 + 0.5 + shift, y as f32 * HEX_ROW_HEIGHT + 0.5]
+    }
+
+    /// Returns the 6 corners of a hexagon counter-clockwise, starting with the north-east corner
+    pub fn get_corners(&self, index: usize) -> [[f32; 2]; 6] {
+        let [x, y] = self.get_center(index);
+
+        [
+            [x + 0.5, y + 0.25],
+            [x, y + 0.5],
+            [x - 0.5, y + 0.25],
+            [x - 0.5, y - 0.25],
+            [x, y - 0.5],
+            [x + 0.5, y - 0.25],
+        ]
+    }
+
+    /// Returns the size of the whole map in tiles
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::hex::HexMap;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = HexMap::new(Size2d::new(4, 3), false);
+    ///
+    /// assert_eq!(map.get_render_size(), [4.5, 2.5]);
+    /// ```
+    pub fn get_render_size(&self) -> [f32; 2] {
+        let width = self.size.width() as f32;
+        let height = self.size.height() as f32;
+        let shift = if self.size.height() > 1 { 0.5 } else { 0.0 };
+
+        [width + shift, (height - 1.0) * HEX_ROW_HEIGHT + 1.0]
+    }
+}
+
+impl CostCalculator<HexDirection> for HexMap {
+    fn is_valid(&self, index: usize) -> bool {
+        !*self.is_occupied.get(index).unwrap_or(&true)
+    }
+
+    fn calculate_cost(&self, _index: usize, _neighbor: &Neighbor<HexDirection>) -> u32 {
+        1
+    }
+}
+
+impl Graph<bool, HexDirection> for HexMap {
+    fn get_size(&self) -> usize {
+        self.size.get_tiles()
+    }
+
+    fn get_node(&self, index: usize) -> Option<&bool> {
+        self.is_occupied.get(index)
+    }
+
+    /// Returns the neighbors in the order of [`HEX_DIRECTIONS`]
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::hex::HexMap;
+    ///# use rust_roguelike_core::math::graph::Graph;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = HexMap::new(Size2d::new(3, 3), false);
+    /// let even: Vec<usize> = map.get_neighbors(1).iter().map(|n| n.index).collect();
+    /// let odd: Vec<usize> = map.get_neighbors(4).iter().map(|n| n.index).collect();
+    ///
+    /// assert_eq!(even, vec![2, 4, 3, 0]);
+    /// assert_eq!(odd, vec![5, 8, 7, 3, 1, 2]);
+    /// ```
+    fn get_neighbors(&self, index: usize) -> Vec<Neighbor<HexDirection>> {
+        self.get_all_neighbors(index)
+            .into_iter()
+            .filter(|neighbor| self.is_valid(neighbor.index))
+            .collect()
+    }
+
+    fn get_all_neighbors(&self, index: usize) -> Vec<Neighbor<HexDirection>> {
+        HEX_DIRECTIONS
+            .iter()
+            .filter_map(|direction| {
+                self.get_neighbor(index, *direction)
+                    .map(|neighbor| Neighbor {
+                        index: neighbor,
+                        edge: *direction,
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the distance between the offset coordinates of 2 hexagons.
+    ///
+    /// Only [`DistanceCalculator::Hex`] returns the number of steps.
+    /// The others assume a square grid, so [`DistanceCalculator::Manhattan`] overestimates it.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::distance::DistanceCalculator;
+    ///# use rust_roguelike_core::math::graph::hex::HexMap;
+    ///# use rust_roguelike_core::math::graph::Graph;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let map = HexMap::new(Size2d::new(4, 4), false);
+    ///
+    /// assert_eq!(map.get_distance(DistanceCalculator::Hex, 0, 15), 5);
+    /// assert_eq!(map.get_distance(DistanceCalculator::Manhattan, 0, 15), 6);
+    /// assert_eq!(map.get_distance(DistanceCalculator::Chebyshev, 0, 15), 3);
+    /// ```
+    fn get_distance(&self, calculator: DistanceCalculator, from: usize, to: usize) -> u32 {
+        let [from_x, from_y] = self.size.to_point(from);
+        let [to_x, to_y] = self.size.to_point(to);
+        calculator.calculate_2d(from_x as i32, from_y as i32, to_x as i32, to_y as i32)
+    }
+}
+
+impl Map2d<bool, HexDirection> for HexMap {
+    fn get_size_2d(&self) -> Size2d {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::connectivity::flood_fill;
+    use crate::algorithm::pathfinding::a_star::{AStar, TieBreaking, DEFAULT_HEURISTIC_WEIGHT};
+    use crate::algorithm::pathfinding::PathfindingResult::*;
+    use crate::algorithm::pathfinding::{Budget, PathfindingAlgorithm};
+
+    #[test]
+    fn test_coordinates_round_trip() {
+        let map = HexMap::new(Size2d::new(7, 6), false);
+
+        for index in 0..map.get_size() {
+            assert_eq!(map.from_axial(map.to_axial(index)), Some(index));
+        }
+    }
+
+    #[test]
+    fn test_neighbors_are_mutual() {
+        let map = HexMap::new(Size2d::new(5, 4), false);
+
+        for index in 0..map.get_size() {
+            for neighbor in map.get_neighbors(index) {
+                assert_eq!(
+                    map.get_distance(DistanceCalculator::Hex, index, neighbor.index),
+                    1
+                );
+                assert!(map
+                    .get_neighbors(neighbor.index)
+                    .iter()
+                    .any(|back| back.index == index));
+            }
+        }
+    }
+
+    #[test]
+    fn test_distance_matches_search() {
+        let map = HexMap::new(Size2d::new(6, 5), false);
+        let algorithm = AStar::new(
+            DistanceCalculator::Hex,
+            Budget::default(),
+            DEFAULT_HEURISTIC_WEIGHT,
+            TieBreaking::default(),
+        );
+
+        for goal in 1..map.get_size() {
+            match algorithm.find(&map, 0, goal) {
+                Path { cost, .. } => {
+                    assert_eq!(cost, map.get_distance(DistanceCalculator::Hex, 0, goal))
+                }
+                result => panic!("Unexpected {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_blocked_neighbors() {
+        // Blocks the neighbors of 12 except the 2 to the south
+        let mut map = HexMap::new(Size2d::new(5, 5), false);
+        map.add_border();
+
+        for direction in &HEX_DIRECTIONS[..4] {
+            let neighbor = map.get_neighbor(12, *direction).unwrap();
+            map.set_node(neighbor, true);
+        }
+
+        let mut region = flood_fill(&map, 12);
+        region.sort_unstable();
+
+        assert_eq!(region, vec![6, 7, 8, 12]);
+    }
+
+    #[test]
+    fn test_from_occupancy() {
+        let mut occupancy = OccupancyMap::new(Size2d::new(4, 3), false);
+        occupancy.add_border();
+        let map = HexMap::from_occupancy(&occupancy);
+
+        assert_eq!(map.is_occupied, occupancy.is_occupied);
+        assert_eq!(map.get_size_2d(), Size2d::new(4, 3));
+    }
+}
@@ -0,0 +1,512 @@
+use crate::algorithm::pathfinding::CostCalculator;
+use crate::math::distance::DistanceCalculator;
+use crate::math::graph::{Graph, Neighbor};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// The ways to move between 2 nodes of a [`LevelGraph`], which are not neighbors in their level
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connector {
+    Stairs,
+    Ladder,
+    Portal,
+}
+
+/// The edge between neighbors in a [`LevelGraph`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LevelEdge<E> {
+    /// An edge inside a level
+    Local(E),
+    /// A connector with its own cost
+    Connector { connector: Connector, cost: u32 },
+}
+
+/// Stacks several levels like the floors of a dungeon into a single graph.
+///
+/// Each node has a global index. The nodes of the first level come first,
+/// then those of the second level and so on.
+/// Connectors like stairs & portals add edges between specific nodes.
+///
+/// The distance between nodes is a lower bound of the cost, which also considers the
+/// cheapest way through any connector, because a portal can be a shortcut even inside the same level.
+///
+/// ```
+///# use rust_roguelike_core::algorithm::pathfinding::a_star::AStar;
+///# use rust_roguelike_core::algorithm::pathfinding::{PathfindingAlgorithm, PathfindingResult};
+///# use rust_roguelike_core::math::graph::levels::{Connector, LevelGraph};
+///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+///# use rust_roguelike_core::math::size2d::Size2d;
+/// let upper = OccupancyMap::new(Size2d::new(3, 1), false);
+/// let lower = OccupancyMap::new(Size2d::new(3, 1), false);
+/// let mut graph = LevelGraph::new(vec![upper, lower]);
+/// let stairs_up = graph.to_global(0, 2);
+/// let stairs_down = graph.to_global(1, 2);
+/// graph.add_two_way_connection(stairs_up, stairs_down, Connector::Stairs, 1);
+///
+/// assert_eq!(graph.to_local(4), (1, 1));
+/// assert_eq!(AStar::default().find(&graph, 0, 3),
+///            PathfindingResult::Path {
+///              indices: vec![1, 2, 5, 4, 3],
+///              cost: 5,
+///            });
+/// ```
+#[derive(Clone, Debug)]
+pub struct LevelGraph<M, N, E> {
+    levels: Vec<M>,
+    offsets: Vec<usize>,
+    connections: HashMap<usize, Vec<(usize, Connector, u32)>>,
+    /// The local indices of the nodes per level, where connectors start
+    exits: Vec<Vec<usize>>,
+    /// The local indices of the nodes per level, where connectors end
+    entries: Vec<Vec<usize>>,
+    min_connector_cost: u32,
+    phantom: PhantomData<(N, E)>,
+}
+
+impl<N, E, M: Graph<N, E>> LevelGraph<M, N, E> {
+    /// Creates a new LevelGraph without connectors
+    pub fn new(levels: Vec<M>) -> Self {
+        let mut offsets = Vec::with_capacity(levels.len());
+        let mut offset = 0;
+
+        for level in &levels {
+            offsets.push(offset);
+            offset += level.get_size();
+        }
+
+        LevelGraph {
+            exits: vec![Vec::new(); levels.len()],
+            entries: vec![Vec::new(); levels.len()],
+            levels,
+            offsets,
+            connections: HashMap::new(),
+            min_connector_cost: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of levels
+    pub fn get_level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns a level
+    pub fn get_level(&self, level: usize) -> Option<&M> {
+        self.levels.get(level)
+    }
+
+    /// Returns a mutable level. Its number of nodes must not change.
+    pub fn get_level_mut(&mut self, level: usize) -> Option<&mut M> {
+        self.levels.get_mut(level)
+    }
+
+    /// Converts the index of a node in a level into its global index
+    pub fn to_global(&self, level: usize, index: usize) -> usize {
+        self.offsets[level] + index
+    }
+
+    /// Converts a global index into the level & the index inside it
+    ///
+    /// # Panics
+    ///
+    /// Panics if the global index is outside the graph.
+    pub fn to_local(&self, global: usize) -> (usize, usize) {
+        assert!(
+            global < self.get_size(),
+            "Global index {} is outside the graph",
+            global
+        );
+        let level = self.offsets.partition_point(|offset| *offset <= global) - 1;
+        (level, global - self.offsets[level])
+    }
+
+    /// Adds a one-way connector between 2 nodes with global indices
+    pub fn add_connection(&mut self, from: usize, to: usize, connector: Connector, cost: u32) {
+        self.connections
+            .entry(from)
+            .or_default()
+            .push((to, connector, cost));
+        self.update_connectors();
+    }
+
+    /// Adds a connector between 2 nodes with global indices, which can be used in both directions
+    pub fn add_two_way_connection(
+        &mut self,
+        from: usize,
+        to: usize,
+        connector: Connector,
+        cost: u32,
+    ) {
+        self.add_connection(from, to, connector, cost);
+        self.add_connection(to, from, connector, cost);
+    }
+
+    /// Removes all connectors starting at a node
+    pub fn remove_connections(&mut self, from: usize) {
+        self.connections.remove(&from);
+        self.update_connectors();
+    }
+
+    /// Returns the connectors starting at a node as pairs of target & connector
+    pub fn get_connections(&self, from: usize) -> Vec<(usize, Connector)> {
+        self.connections
+            .get(&from)
+            .map(|connections| {
+                connections
+                    .iter()
+                    .map(|(to, connector, _)| (*to, *connector))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn update_connectors(&mut self) {
+        let mut exits = vec![Vec::new(); self.levels.len()];
+        let mut entries = vec![Vec::new(); self.levels.len()];
+        let mut min_connector_cost = u32::MAX;
+
+        for (from, connections) in &self.connections {
+            let (level, local) = self.to_local(*from);
+            exits[level].push(local);
+
+            for (to, _, cost) in connections {
+                let (level, local) = self.to_local(*to);
+                entries[level].push(local);
+                min_connector_cost = min_connector_cost.min(*cost);
+            }
+        }
+
+        for indices in exits.iter_mut().chain(entries.iter_mut()) {
+            indices.sort_unstable();
+            indices.dedup();
+        }
+
+        self.exits = exits;
+        self.entries = entries;
+        self.min_connector_cost = min_connector_cost;
+    }
+
+    /// Returns a lower bound of the cost of all paths using connectors or `None` without such paths.
+    ///
+    /// Each of those paths leaves the start's level at an exit, uses at least one connector
+    /// and reaches the goal from an entry in the goal's level.
+    fn get_distance_via_connectors(
+        &self,
+        calculator: DistanceCalculator,
+        (from_level, from): (usize, usize),
+        (to_level, to): (usize, usize),
+    ) -> Option<u32> {
+        let to_exit = self.exits[from_level]
+            .iter()
+            .map(|exit| self.levels[from_level].get_distance(calculator, from, *exit))
+            .min()?;
+        let from_entry = self.entries[to_level]
+            .iter()
+            .map(|entry| self.levels[to_level].get_distance(calculator, *entry, to))
+            .min()?;
+
+        Some(
+            to_exit
+                .saturating_add(self.min_connector_cost)
+                .saturating_add(from_entry),
+        )
+    }
+
+    fn collect_neighbors(&self, index: usize, include_all: bool) -> Vec<Neighbor<LevelEdge<E>>> {
+        if index >= self.get_size() {
+            return Vec::new();
+        }
+
+        let (level, local) = self.to_local(index);
+        let offset = self.offsets[level];
+        let local_neighbors = if include_all {
+            self.levels[level].get_all_neighbors(local)
+        } else {
+            self.levels[level].get_neighbors(local)
+        };
+        let mut neighbors: Vec<Neighbor<LevelEdge<E>>> = local_neighbors
+            .into_iter()
+            .map(|neighbor| Neighbor {
+                index: offset + neighbor.index,
+                edge: LevelEdge::Local(neighbor.edge),
+            })
+            .collect();
+
+        if let Some(connections) = self.connections.get(&index) {
+            for (to, connector, cost) in connections {
+                neighbors.push(Neighbor {
+                    index: *to,
+                    edge: LevelEdge::Connector {
+                        connector: *connector,
+                        cost: *cost,
+                    },
+                });
+            }
+        }
+
+        neighbors
+    }
+}
+
+impl<N, E, M: Graph<N, E>> Graph<N, LevelEdge<E>> for LevelGraph<M, N, E> {
+    fn get_size(&self) -> usize {
+        self.offsets.last().cloned().unwrap_or(0)
+            + self
+                .levels
+                .last()
+                .map(|level| level.get_size())
+                .unwrap_or(0)
+    }
+
+    fn get_node(&self, index: usize) -> Option<&N> {
+        if index >= self.get_size() {
+            return None;
+        }
+
+        let (level, local) = self.to_local(index);
+        self.levels[level].get_node(local)
+    }
+
+    fn get_neighbors(&self, index: usize) -> Vec<Neighbor<LevelEdge<E>>> {
+        self.collect_neighbors(index, false)
+    }
+
+    fn get_all_neighbors(&self, index: usize) -> Vec<Neighbor<LevelEdge<E>>> {
+        self.collect_neighbors(index, true)
+    }
+
+    fn get_distance(&self, calculator: DistanceCalculator, from: usize, to: usize) -> u32 {
+        let from = self.to_local(from);
+        let to = self.to_local(to);
+        let via_connectors = self.get_distance_via_connectors(calculator, from, to);
+
+        if from.0 == to.0 {
+            let local = self.levels[from.0].get_distance(calculator, from.1, to.1);
+
+            match via_connectors {
+                Some(distance) => distance.min(local),
+                None => local,
+            }
+        } else {
+            via_connectors.unwrap_or(0)
+        }
+    }
+}
+
+impl<N, E: Copy, M: Graph<N, E> + CostCalculator<E>> CostCalculator<LevelEdge<E>>
+    for LevelGraph<M, N, E>
+{
+    fn is_valid(&self, index: usize) -> bool {
+        if index >= self.get_size() {
+            return false;
+        }
+
+        let (level, local) = self.to_local(index);
+        self.levels[level].is_valid(local)
+    }
+
+    fn calculate_cost(&self, index: usize, neighbor: &Neighbor<LevelEdge<E>>) -> u32 {
+        match neighbor.edge {
+            LevelEdge::Local(edge) => {
+                let (level, local) = self.to_local(index);
+                let local_neighbor = Neighbor {
+                    index: neighbor.index - self.offsets[level],
+                    edge,
+                };
+                self.levels[level].calculate_cost(local, &local_neighbor)
+            }
+            LevelEdge::Connector { cost, .. } => cost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::pathfinding::a_star::{AStar, TieBreaking, DEFAULT_HEURISTIC_WEIGHT};
+    use crate::algorithm::pathfinding::context::SearchContext;
+    use crate::algorithm::pathfinding::trace::SearchRecorder;
+    use crate::algorithm::pathfinding::PathfindingResult::*;
+    use crate::algorithm::pathfinding::{Budget, PathfindingAlgorithm};
+    use crate::math::graph::cost::CostMap;
+    use crate::math::graph::map2d::Direction2d;
+    use crate::math::graph::occupancy::OccupancyMap;
+    use crate::math::size2d::Size2d;
+
+    fn create_floor() -> OccupancyMap {
+        let mut map = OccupancyMap::new(Size2d::new(5, 3), false);
+        map.add_border();
+        map
+    }
+
+    #[test]
+    fn test_global_indices() {
+        let graph = LevelGraph::new(vec![
+            OccupancyMap::new(Size2d::new(2, 2), false),
+            OccupancyMap::new(Size2d::new(3, 2), false),
+            OccupancyMap::new(Size2d::new(1, 1), false),
+        ]);
+
+        assert_eq!(graph.get_size(), 11);
+        assert_eq!(graph.to_global(1, 2), 6);
+        assert_eq!(graph.to_local(3), (0, 3));
+        assert_eq!(graph.to_local(4), (1, 0));
+        assert_eq!(graph.to_local(10), (2, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_to_local_outside() {
+        let graph = LevelGraph::new(vec![OccupancyMap::new(Size2d::new(2, 2), false)]);
+        graph.to_local(4);
+    }
+
+    #[test]
+    fn test_distance() {
+        let mut graph = LevelGraph::new(vec![OccupancyMap::new(Size2d::new(10, 1), false)]);
+
+        assert_eq!(graph.get_distance(DistanceCalculator::Manhattan, 1, 9), 8);
+
+        graph.add_connection(0, 8, Connector::Portal, 1);
+
+        assert_eq!(graph.get_distance(DistanceCalculator::Manhattan, 1, 9), 3);
+        assert_eq!(graph.get_distance(DistanceCalculator::Manhattan, 1, 4), 3);
+
+        graph.remove_connections(0);
+
+        assert_eq!(graph.get_distance(DistanceCalculator::Manhattan, 1, 9), 8);
+    }
+
+    #[test]
+    fn test_distance_between_levels() {
+        let mut graph = LevelGraph::new(vec![
+            OccupancyMap::new(Size2d::new(10, 1), false),
+            OccupancyMap::new(Size2d::new(10, 1), false),
+        ]);
+
+        assert_eq!(graph.get_distance(DistanceCalculator::Manhattan, 0, 19), 0);
+
+        graph.add_two_way_connection(5, 12, Connector::Stairs, 3);
+        graph.add_connection(9, 17, Connector::Portal, 2);
+
+        assert_eq!(graph.get_distance(DistanceCalculator::Manhattan, 0, 19), 9);
+        assert_eq!(graph.get_distance(DistanceCalculator::Manhattan, 19, 0), 14);
+    }
+
+    #[test]
+    fn test_heuristic_expands_fewer_nodes() {
+        let floor = || {
+            let mut map = OccupancyMap::new(Size2d::new(20, 20), false);
+            map.add_border();
+            map
+        };
+        let mut graph = LevelGraph::new(vec![floor(), floor()]);
+        graph.add_two_way_connection(21, 421, Connector::Stairs, 2);
+        let start = graph.to_global(0, 361);
+        let goal = graph.to_global(0, 378);
+        let trace = |heuristic_weight| {
+            let algorithm = AStar::new(
+                DistanceCalculator::Manhattan,
+                Budget::default(),
+                heuristic_weight,
+                TieBreaking::default(),
+            );
+            let mut recorder = SearchRecorder::default();
+            algorithm.find_with_tracer(
+                &mut SearchContext::default(),
+                &graph,
+                start,
+                goal,
+                &mut recorder,
+            );
+            recorder
+        };
+
+        let with_heuristic = trace(DEFAULT_HEURISTIC_WEIGHT);
+        let without_heuristic = trace(0);
+
+        assert_eq!(with_heuristic.result, without_heuristic.result);
+        assert!(with_heuristic.expanded.len() * 4 < without_heuristic.expanded.len());
+    }
+
+    #[test]
+    fn test_portal_shortcut_in_same_level() {
+        let mut graph = LevelGraph::new(vec![OccupancyMap::new(Size2d::new(10, 1), false)]);
+        graph.add_connection(0, 8, Connector::Portal, 1);
+
+        assert_eq!(
+            AStar::default().find(&graph, 1, 9),
+            Path {
+                indices: vec![0, 8, 9],
+                cost: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_neighbors_with_connector() {
+        let mut graph = LevelGraph::new(vec![create_floor(), create_floor()]);
+        graph.add_connection(8, 21, Connector::Ladder, 3);
+
+        let neighbors = graph.get_neighbors(8);
+
+        assert_eq!(neighbors.len(), 2);
+        assert_eq!(neighbors[0].index, 7);
+        assert_eq!(neighbors[0].edge, LevelEdge::Local(Direction2d::WEST));
+        assert_eq!(neighbors[1].index, 21);
+        assert_eq!(graph.calculate_cost(8, &neighbors[1]), 3);
+        assert_eq!(graph.get_all_neighbors(8).len(), 5);
+        assert!(graph.get_neighbors(21).iter().all(|n| n.index >= 15));
+        assert_eq!(graph.get_connections(8), vec![(21, Connector::Ladder)]);
+    }
+
+    #[test]
+    fn test_one_way_portal() {
+        let mut graph = LevelGraph::new(vec![create_floor(), create_floor()]);
+        graph.add_connection(6, 23, Connector::Portal, 1);
+        let algorithm = AStar::default();
+
+        assert_eq!(
+            algorithm.find(&graph, 6, 22),
+            Path {
+                indices: vec![23, 22],
+                cost: 2,
+            }
+        );
+        assert_eq!(algorithm.find(&graph, 22, 6), NoPathFound);
+    }
+
+    #[test]
+    fn test_monster_follows_player_downstairs() {
+        // The player went down the stairs at the east end of level 0 and moved west.
+        let mut graph = LevelGraph::new(vec![create_floor(), create_floor(), create_floor()]);
+        graph.add_two_way_connection(8, 23, Connector::Stairs, 2);
+        graph.add_two_way_connection(21, 36, Connector::Stairs, 2);
+        let monster = 6;
+        let player = graph.to_global(1, 6);
+
+        assert_eq!(
+            AStar::default().find(&graph, monster, player),
+            Path {
+                indices: vec![7, 8, 23, 22, 21],
+                cost: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn test_costs_of_levels() {
+        let mut swamp = CostMap::new(Size2d::new(3, 1), 5);
+        swamp.set_cost(0, 1);
+        let mut graph = LevelGraph::new(vec![CostMap::new(Size2d::new(3, 1), 1), swamp]);
+        graph.add_two_way_connection(0, 3, Connector::Stairs, 1);
+
+        assert_eq!(
+            AStar::default().find(&graph, 2, 5),
+            Path {
+                indices: vec![1, 0, 3, 4, 5],
+                cost: 13,
+            }
+        );
+    }
+}
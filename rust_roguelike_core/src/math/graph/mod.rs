@@ -1,5 +1,8 @@
 use crate::math::distance::DistanceCalculator;
 
+pub mod cost;
+pub mod hex;
+pub mod levels;
 pub mod map2d;
 pub mod occupancy;
 
@@ -14,6 +17,14 @@ pub trait Graph<N, E> {
     /// Returns the neighbors of a node
     fn get_neighbors(&self, index: usize) -> Vec<Neighbor<E>>;
 
+    /// Returns the neighbors of a node including those, which can not be entered.
+    ///
+    /// Used by a [`MovementProfile`](crate::algorithm::pathfinding::profile::MovementProfile)
+    /// to let agents like ghosts enter them.
+    fn get_all_neighbors(&self, index: usize) -> Vec<Neighbor<E>> {
+        self.get_neighbors(index)
+    }
+
     /// Returns the distance between 2 nodes of the graph
     fn get_distance(&self, calculator: DistanceCalculator, from: usize, to: usize) -> u32;
 }
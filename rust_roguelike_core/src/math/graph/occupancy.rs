@@ -2,6 +2,7 @@ use crate::algorithm::pathfinding::CostCalculator;
 use crate::math::distance::DistanceCalculator;
 use crate::math::graph::map2d::{Direction2d, Map2d};
 use crate::math::graph::{Graph, Neighbor};
+use crate::math::random::Random;
 use crate::math::size2d::Size2d;
 
 #[derive(Default)]
@@ -45,10 +46,45 @@ impl OccupancyMap {
         }
     }
 
+    pub fn fill_rectangle(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        width: u32,
+        height: u32,
+        value: bool,
+    ) {
+        for y in start_y..(start_y + height) {
+            for x in start_x..(start_x + width) {
+                self.is_occupied[self.size.to_index(x, y)] = value;
+            }
+        }
+    }
+
     pub fn set_node(&mut self, index: usize, value: bool) {
         self.is_occupied[index] = value;
     }
 
+    /// Occupies each node with a probability of `percentage` percent, e.g. for test maps
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::graph::occupancy::OccupancyMap;
+    ///# use rust_roguelike_core::math::random::Random;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let mut map = OccupancyMap::new(Size2d::new(20, 15), false);
+    /// map.add_random_nodes(&mut Random::new(7), 25);
+    /// let occupied = map.is_occupied.iter().filter(|o| **o).count();
+    ///
+    /// assert!(occupied > 50 && occupied < 100);
+    /// ```
+    pub fn add_random_nodes(&mut self, random: &mut Random, percentage: u32) {
+        for is_occupied in self.is_occupied.iter_mut() {
+            if random.check_percentage(percentage) {
+                *is_occupied = true;
+            }
+        }
+    }
+
     fn add_neighbor(
         &self,
         neighbors: &mut Vec<Neighbor<Direction2d>>,
@@ -57,11 +93,11 @@ impl OccupancyMap {
         dx: i32,
         dy: i32,
     ) {
-        let index = self
-            .size
-            .to_index((point[0] + dx) as u32, (point[1] + dy) as u32);
+        let x = point[0] + dx;
+        let y = point[1] + dy;
 
-        if self.is_valid(index) {
+        if x >= 0 && y >= 0 && x < self.size.width() as i32 && y < self.size.height() as i32 {
+            let index = self.size.to_index(x as u32, y as u32);
             neighbors.push(Neighbor { index, edge: dir });
         }
     }
@@ -87,6 +123,13 @@ impl Graph<bool, Direction2d> for OccupancyMap {
     }
 
     fn get_neighbors(&self, index: usize) -> Vec<Neighbor<Direction2d>> {
+        self.get_all_neighbors(index)
+            .into_iter()
+            .filter(|neighbor| self.is_valid(neighbor.index))
+            .collect()
+    }
+
+    fn get_all_neighbors(&self, index: usize) -> Vec<Neighbor<Direction2d>> {
         let [x, y] = self.size.to_point(index);
         let point = [x as i32, y as i32];
         let mut neighbors = Vec::new();
@@ -0,0 +1,34 @@
+/// Converts offset coordinates `[x, y]` of a [`HexMap`](crate::math::graph::hex::HexMap) into axial coordinates `[q, r]`
+///
+/// ```
+///# use rust_roguelike_core::math::hex::{axial_to_offset, offset_to_axial};
+/// assert_eq!(offset_to_axial([3, 0]), [3, 0]);
+/// assert_eq!(offset_to_axial([3, 1]), [3, 1]);
+/// assert_eq!(offset_to_axial([3, 2]), [2, 2]);
+/// assert_eq!(offset_to_axial([3, 5]), [1, 5]);
+/// assert_eq!(axial_to_offset(offset_to_axial([4, 7])), [4, 7]);
+/// ```
+pub fn offset_to_axial(point: [i32; 2]) -> [i32; 2] {
+    let [x, y] = point;
+    [x - (y - (y & 1)) / 2, y]
+}
+
+/// Converts axial coordinates `[q, r]` into offset coordinates `[x, y]` of a [`HexMap`](crate::math::graph::hex::HexMap)
+pub fn axial_to_offset(axial: [i32; 2]) -> [i32; 2] {
+    let [q, r] = axial;
+    [q + (r - (r & 1)) / 2, r]
+}
+
+/// Returns the number of steps between 2 hexagons in axial coordinates
+///
+/// ```
+///# use rust_roguelike_core::math::hex::calculate_hex_distance;
+/// assert_eq!(calculate_hex_distance([0, 0], [2, 0]), 2);
+/// assert_eq!(calculate_hex_distance([0, 0], [-2, 3]), 3);
+/// assert_eq!(calculate_hex_distance([1, -1], [-1, 1]), 2);
+/// ```
+pub fn calculate_hex_distance(from: [i32; 2], to: [i32; 2]) -> u32 {
+    let dq = to[0] - from[0];
+    let dr = to[1] - from[1];
+    ((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as u32
+}
@@ -1,4 +1,8 @@
 pub mod color;
 pub mod distance;
 pub mod graph;
+pub mod hex;
+pub mod noise;
+pub mod random;
+pub mod rectangle;
 pub mod size2d;
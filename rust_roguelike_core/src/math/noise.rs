@@ -0,0 +1,296 @@
+use crate::math::random::Random;
+use crate::math::size2d::Size2d;
+use std::f64::consts::FRAC_1_SQRT_2;
+
+/// The gradients used by Perlin & Simplex noise
+const GRADIENTS: [[f64; 2]; 8] = [
+    [1.0, 0.0],
+    [-1.0, 0.0],
+    [0.0, 1.0],
+    [0.0, -1.0],
+    [FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+    [-FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+    [FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+    [-FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+];
+
+/// Different kinds of coherent noise
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoiseType {
+    /// Interpolates random values at integer coordinates.
+    Value,
+    /// See [Wikipedia](https://en.wikipedia.org/wiki/Perlin_noise)
+    Perlin,
+    /// See [Wikipedia](https://en.wikipedia.org/wiki/Simplex_noise)
+    Simplex,
+}
+
+/// A source of coherent 2d noise, which is seeded with a random permutation
+#[derive(Clone, Debug)]
+pub struct Noise {
+    noise_type: NoiseType,
+    permutation: Vec<usize>,
+}
+
+impl Noise {
+    /// Creates a new source of noise
+    pub fn new(noise_type: NoiseType, seed: u64) -> Noise {
+        let mut random = Random::new(seed);
+        let mut permutation: Vec<usize> = (0..256).collect();
+        random.shuffle(&mut permutation);
+        permutation.extend_from_within(..);
+
+        Noise {
+            noise_type,
+            permutation,
+        }
+    }
+
+    /// Returns the noise at a point, which is between -1 & 1
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::noise::{Noise, NoiseType};
+    /// let noise = Noise::new(NoiseType::Perlin, 42);
+    ///
+    /// assert_eq!(noise.get(3.0, 4.0), 0.0);
+    /// assert_eq!(noise.get(3.2, 4.7), Noise::new(NoiseType::Perlin, 42).get(3.2, 4.7));
+    /// ```
+    pub fn get(&self, x: f64, y: f64) -> f64 {
+        match self.noise_type {
+            NoiseType::Value => self.get_value(x, y),
+            NoiseType::Perlin => self.get_perlin(x, y),
+            NoiseType::Simplex => self.get_simplex(x, y),
+        }
+    }
+
+    fn hash(&self, x: i64, y: i64) -> usize {
+        let x = (x & 255) as usize;
+        let y = (y & 255) as usize;
+        self.permutation[self.permutation[x] + y]
+    }
+
+    fn get_value(&self, x: f64, y: f64) -> f64 {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let (ix, iy) = (x0 as i64, y0 as i64);
+        let u = fade(x - x0);
+        let v = fade(y - y0);
+        let value = |x: i64, y: i64| self.hash(x, y) as f64 / 127.5 - 1.0;
+
+        lerp(
+            lerp(value(ix, iy), value(ix + 1, iy), u),
+            lerp(value(ix, iy + 1), value(ix + 1, iy + 1), u),
+            v,
+        )
+    }
+
+    fn get_perlin(&self, x: f64, y: f64) -> f64 {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let (ix, iy) = (x0 as i64, y0 as i64);
+        let (dx, dy) = (x - x0, y - y0);
+        let u = fade(dx);
+        let v = fade(dy);
+        let gradient = |gx: i64, gy: i64, x: f64, y: f64| {
+            let [a, b] = GRADIENTS[self.hash(gx, gy) % GRADIENTS.len()];
+            a * x + b * y
+        };
+
+        let value = lerp(
+            lerp(
+                gradient(ix, iy, dx, dy),
+                gradient(ix + 1, iy, dx - 1.0, dy),
+                u,
+            ),
+            lerp(
+                gradient(ix, iy + 1, dx, dy - 1.0),
+                gradient(ix + 1, iy + 1, dx - 1.0, dy - 1.0),
+                u,
+            ),
+            v,
+        );
+
+        // The theoretical maximum of 2d perlin noise is sqrt(0.5)
+        (value * std::f64::consts::SQRT_2).clamp(-1.0, 1.0)
+    }
+
+    /// Based on [Stefan Gustavson's paper](https://weber.itn.liu.se/~stegu/simplexnoise/simplexnoise.pdf)
+    fn get_simplex(&self, x: f64, y: f64) -> f64 {
+        let f2 = 0.5 * (3.0f64.sqrt() - 1.0);
+        let g2 = (3.0 - 3.0f64.sqrt()) / 6.0;
+
+        let skew = (x + y) * f2;
+        let i = (x + skew).floor();
+        let j = (y + skew).floor();
+        let unskew = (i + j) * g2;
+        let x0 = x - (i - unskew);
+        let y0 = y - (j - unskew);
+
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let x1 = x0 - i1 as f64 + g2;
+        let y1 = y0 - j1 as f64 + g2;
+        let x2 = x0 - 1.0 + 2.0 * g2;
+        let y2 = y0 - 1.0 + 2.0 * g2;
+
+        let (ii, jj) = (i as i64, j as i64);
+        let corner = |gx: i64, gy: i64, x: f64, y: f64| {
+            let t = 0.5 - x * x - y * y;
+
+            if t < 0.0 {
+                0.0
+            } else {
+                let [a, b] = GRADIENTS[self.hash(gx, gy) % GRADIENTS.len()];
+                t.powi(4) * (a * x + b * y)
+            }
+        };
+
+        let value = corner(ii, jj, x0, y0)
+            + corner(ii + i1, jj + j1, x1, y1)
+            + corner(ii + 1, jj + 1, x2, y2);
+
+        (70.0 * value).clamp(-1.0, 1.0)
+    }
+}
+
+/// Combines several octaves of noise with increasing frequency & decreasing amplitude.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Fractional_Brownian_motion)
+#[derive(Clone, Debug)]
+pub struct FractalNoise {
+    noise: Noise,
+    octaves: u32,
+    frequency: f64,
+    lacunarity: f64,
+    gain: f64,
+}
+
+impl FractalNoise {
+    /// Creates a new FractalNoise
+    ///
+    /// * `octaves` - the number of combined layers
+    /// * `frequency` - the frequency of the first octave
+    /// * `lacunarity` - multiplies the frequency of each following octave
+    /// * `gain` - multiplies the amplitude of each following octave
+    pub fn new(noise: Noise, octaves: u32, frequency: f64, lacunarity: f64, gain: f64) -> Self {
+        FractalNoise {
+            noise,
+            octaves: octaves.max(1),
+            frequency,
+            lacunarity,
+            gain,
+        }
+    }
+
+    /// Creates a new FractalNoise with 4 octaves, a frequency of 0.05, a lacunarity of 2 & a gain of 0.5
+    pub fn with_defaults(noise_type: NoiseType, seed: u64) -> Self {
+        FractalNoise::new(Noise::new(noise_type, seed), 4, 0.05, 2.0, 0.5)
+    }
+
+    /// Returns the noise at a point, which is between -1 & 1
+    pub fn get(&self, x: f64, y: f64) -> f64 {
+        let mut frequency = self.frequency;
+        let mut amplitude = 1.0;
+        let mut total_amplitude = 0.0;
+        let mut value = 0.0;
+
+        for _ in 0..self.octaves {
+            value += self.noise.get(x * frequency, y * frequency) * amplitude;
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+
+        value / total_amplitude
+    }
+
+    /// Returns the noise for each tile of an area, which is normalized to be between 0 & 1
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::noise::{FractalNoise, NoiseType};
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let noise = FractalNoise::with_defaults(NoiseType::Simplex, 3);
+    /// let values = noise.generate(Size2d::new(40, 30));
+    ///
+    /// assert_eq!(values.len(), 1200);
+    /// assert_eq!(values.iter().cloned().fold(f64::MAX, f64::min), 0.0);
+    /// assert_eq!(values.iter().cloned().fold(f64::MIN, f64::max), 1.0);
+    /// ```
+    pub fn generate(&self, size: Size2d) -> Vec<f64> {
+        let mut values: Vec<f64> = (0..size.get_tiles())
+            .map(|index| {
+                let [x, y] = size.to_point(index);
+                self.get(x as f64, y as f64)
+            })
+            .collect();
+
+        normalize(&mut values);
+
+        values
+    }
+}
+
+/// Scales the values to be between 0 & 1
+fn normalize(values: &mut [f64]) {
+    let min = values.iter().cloned().fold(f64::MAX, f64::min);
+    let max = values.iter().cloned().fold(f64::MIN, f64::max);
+    let range = max - min;
+
+    for value in values.iter_mut() {
+        *value = if range > 0.0 {
+            (*value - min) / range
+        } else {
+            0.0
+        };
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + t * (b - a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TYPES: [NoiseType; 3] = [NoiseType::Value, NoiseType::Perlin, NoiseType::Simplex];
+
+    #[test]
+    fn test_range() {
+        for noise_type in TYPES.iter() {
+            let noise = Noise::new(*noise_type, 17);
+
+            for i in 0..1000 {
+                let value = noise.get(i as f64 * 0.137, i as f64 * 0.291 - 50.0);
+                assert!((-1.0..=1.0).contains(&value), "{:?}", noise_type);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_coherent() {
+        for noise_type in TYPES.iter() {
+            let noise = Noise::new(*noise_type, 5);
+
+            for i in 0..100 {
+                let x = i as f64 * 0.37;
+                let difference = (noise.get(x, 2.5) - noise.get(x + 0.001, 2.5)).abs();
+                assert!(difference < 0.05, "{:?}", noise_type);
+            }
+        }
+    }
+
+    #[test]
+    fn test_different_seeds() {
+        for noise_type in TYPES.iter() {
+            let a = FractalNoise::with_defaults(*noise_type, 1).generate(Size2d::new(10, 10));
+            let b = FractalNoise::with_defaults(*noise_type, 2).generate(Size2d::new(10, 10));
+
+            assert_ne!(a, b);
+        }
+    }
+}
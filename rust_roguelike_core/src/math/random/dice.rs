@@ -0,0 +1,344 @@
+use crate::math::random::Random;
+use std::fmt;
+use std::str::FromStr;
+
+/// The maximum number of dice, which can be rolled at once
+pub const MAX_DICE: u32 = 1000;
+
+/// How often dice are rolled and which result is kept
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RollMode {
+    /// Roll once
+    Normal,
+    /// Roll twice and keep the higher total
+    Advantage,
+    /// Roll twice and keep the lower total
+    Disadvantage,
+}
+
+/// The errors of parsing [`Dice`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseDiceError {
+    /// The 'd' between count & sides is missing
+    MissingSeparator,
+    /// The number of dice is no number or above [`MAX_DICE`]
+    InvalidCount,
+    InvalidSides,
+    InvalidModifier,
+    InvalidMode,
+}
+
+impl fmt::Display for ParseDiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            ParseDiceError::MissingSeparator => "missing 'd' between count and sides",
+            ParseDiceError::InvalidCount => "invalid number of dice",
+            ParseDiceError::InvalidSides => "invalid number of sides",
+            ParseDiceError::InvalidModifier => "invalid modifier",
+            ParseDiceError::InvalidMode => "invalid mode, expected advantage or disadvantage",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Dice in the common notation like `3d6+2` or `1d20 advantage`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dice {
+    count: u32,
+    sides: u32,
+    modifier: i32,
+    mode: RollMode,
+}
+
+/// The breakdown of a roll
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiceRoll {
+    /// The result of each kept die
+    pub rolls: Vec<u32>,
+    /// The result of each die discarded by advantage or disadvantage
+    pub discarded: Vec<u32>,
+    pub modifier: i32,
+    pub total: i32,
+}
+
+impl Dice {
+    /// Creates new dice. The count is limited to [`MAX_DICE`].
+    pub fn new(count: u32, sides: u32, modifier: i32, mode: RollMode) -> Dice {
+        Dice {
+            count: count.min(MAX_DICE),
+            sides,
+            modifier,
+            mode,
+        }
+    }
+
+    /// Returns the lowest possible total
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::dice::Dice;
+    /// let dice: Dice = "3d6+2".parse().unwrap();
+    /// assert_eq!(dice.min(), 5);
+    /// ```
+    pub fn min(&self) -> i32 {
+        to_i32(u64::from(self.count)).saturating_add(self.modifier)
+    }
+
+    /// Returns the highest possible total. Totals saturate at the limits of `i32`.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::dice::Dice;
+    /// let dice: Dice = "3d6+2".parse().unwrap();
+    /// assert_eq!(dice.max(), 20);
+    ///
+    /// let dice: Dice = "1000d4294967295".parse().unwrap();
+    /// assert_eq!(dice.max(), i32::MAX);
+    /// ```
+    pub fn max(&self) -> i32 {
+        to_i32(u64::from(self.count) * u64::from(self.sides)).saturating_add(self.modifier)
+    }
+
+    /// Rolls the dice
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::dice::Dice;
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let dice: Dice = "1d20 advantage".parse().unwrap();
+    /// let mut random = Random::new(42);
+    /// let roll = dice.roll(&mut random);
+    ///
+    /// assert_eq!(roll.rolls.len(), 1);
+    /// assert_eq!(roll.discarded.len(), 1);
+    /// assert!(roll.rolls[0] >= roll.discarded[0]);
+    /// assert_eq!(roll.total, roll.rolls[0] as i32);
+    /// ```
+    pub fn roll(&self, random: &mut Random) -> DiceRoll {
+        let rolls = self.roll_once(random);
+
+        let (rolls, discarded) = match self.mode {
+            RollMode::Normal => (rolls, Vec::new()),
+            RollMode::Advantage | RollMode::Disadvantage => {
+                let other = self.roll_once(random);
+                let is_first_higher = sum(&rolls) >= sum(&other);
+
+                if is_first_higher == (self.mode == RollMode::Advantage) {
+                    (rolls, other)
+                } else {
+                    (other, rolls)
+                }
+            }
+        };
+
+        let total = to_i32(sum(&rolls)).saturating_add(self.modifier);
+
+        DiceRoll {
+            rolls,
+            discarded,
+            modifier: self.modifier,
+            total,
+        }
+    }
+
+    fn roll_once(&self, random: &mut Random) -> Vec<u32> {
+        (0..self.count)
+            .map(|_| random.gen_range(0, self.sides) + 1)
+            .collect()
+    }
+}
+
+fn sum(rolls: &[u32]) -> u64 {
+    rolls
+        .iter()
+        .fold(0u64, |sum, roll| sum.saturating_add(u64::from(*roll)))
+}
+
+fn to_i32(value: u64) -> i32 {
+    value.min(i32::MAX as u64) as i32
+}
+
+/// Parses dice like `d6`, `3d6+2`, `2d8-1`, `1d20 advantage` or `1d20 disadvantage`
+///
+/// ```
+///# use rust_roguelike_core::math::random::dice::{Dice, ParseDiceError, RollMode};
+/// assert_eq!("3d6+2".parse(), Ok(Dice::new(3, 6, 2, RollMode::Normal)));
+/// assert_eq!("d8 - 1".parse(), Ok(Dice::new(1, 8, -1, RollMode::Normal)));
+/// assert_eq!("1d20 advantage".parse(), Ok(Dice::new(1, 20, 0, RollMode::Advantage)));
+/// assert_eq!("2D4 + 1 disadvantage".parse(), Ok(Dice::new(2, 4, 1, RollMode::Disadvantage)));
+/// assert_eq!("36".parse::<Dice>(), Err(ParseDiceError::MissingSeparator));
+/// assert_eq!("xd6".parse::<Dice>(), Err(ParseDiceError::InvalidCount));
+/// assert_eq!("4294967295d6".parse::<Dice>(), Err(ParseDiceError::InvalidCount));
+/// assert_eq!("1d0".parse::<Dice>(), Err(ParseDiceError::InvalidSides));
+/// assert_eq!("1d6+a".parse::<Dice>(), Err(ParseDiceError::InvalidModifier));
+/// assert_eq!("1d6++2".parse::<Dice>(), Err(ParseDiceError::InvalidModifier));
+/// assert_eq!("1d6 lucky".parse::<Dice>(), Err(ParseDiceError::InvalidMode));
+/// ```
+impl FromStr for Dice {
+    type Err = ParseDiceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.to_lowercase();
+        let mut words: Vec<&str> = text.split_whitespace().collect();
+
+        let mode = match words.last() {
+            Some(word) if words.len() > 1 && word.chars().all(char::is_alphabetic) => {
+                let mode = match *word {
+                    "advantage" | "adv" => RollMode::Advantage,
+                    "disadvantage" | "dis" => RollMode::Disadvantage,
+                    _ => return Err(ParseDiceError::InvalidMode),
+                };
+                words.pop();
+                mode
+            }
+            _ => RollMode::Normal,
+        };
+
+        let expression = words.concat();
+
+        let (count, rest) = expression
+            .split_once('d')
+            .ok_or(ParseDiceError::MissingSeparator)?;
+
+        let count = if count.is_empty() {
+            1
+        } else {
+            count.parse().map_err(|_| ParseDiceError::InvalidCount)?
+        };
+
+        if count > MAX_DICE {
+            return Err(ParseDiceError::InvalidCount);
+        }
+
+        let (sides, modifier) = match rest.find(['+', '-']) {
+            None => (rest, 0),
+            Some(position) => {
+                let digits = &rest[(position + 1)..];
+
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(ParseDiceError::InvalidModifier);
+                }
+
+                let modifier = rest[position..]
+                    .strip_prefix('+')
+                    .unwrap_or(&rest[position..])
+                    .parse()
+                    .map_err(|_| ParseDiceError::InvalidModifier)?;
+                (&rest[..position], modifier)
+            }
+        };
+
+        let sides = sides.parse().map_err(|_| ParseDiceError::InvalidSides)?;
+
+        if sides == 0 {
+            return Err(ParseDiceError::InvalidSides);
+        }
+
+        Ok(Dice::new(count, sides, modifier, mode))
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}d{}", self.count, self.sides)?;
+
+        if self.modifier != 0 {
+            write!(f, "{:+}", self.modifier)?;
+        }
+
+        match self.mode {
+            RollMode::Normal => Ok(()),
+            RollMode::Advantage => write!(f, " advantage"),
+            RollMode::Disadvantage => write!(f, " disadvantage"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roll_within_limits() {
+        let dice: Dice = "3d6+2".parse().unwrap();
+        let mut random = Random::new(3);
+
+        for _ in 0..100 {
+            let roll = dice.roll(&mut random);
+
+            assert_eq!(roll.rolls.len(), 3);
+            assert!(roll.discarded.is_empty());
+            assert!(roll.rolls.iter().all(|r| *r >= 1 && *r <= 6));
+            assert_eq!(roll.total, roll.rolls.iter().sum::<u32>() as i32 + 2);
+            assert!(roll.total >= dice.min() && roll.total <= dice.max());
+        }
+    }
+
+    #[test]
+    fn test_disadvantage() {
+        let dice: Dice = "2d10 disadvantage".parse().unwrap();
+        let mut random = Random::new(8);
+
+        for _ in 0..100 {
+            let roll = dice.roll(&mut random);
+
+            assert!(sum(&roll.rolls) <= sum(&roll.discarded));
+        }
+    }
+
+    #[test]
+    fn test_extreme_values() {
+        let mut random = Random::new(1);
+        let dice: Dice = "1d4294967295".parse().unwrap();
+        let roll = dice.roll(&mut random);
+
+        assert!(roll.rolls[0] >= 1);
+        assert_eq!(dice.max(), i32::MAX);
+
+        let dice: Dice = "1d6+2147483647".parse().unwrap();
+
+        assert_eq!(dice.roll(&mut random).total, i32::MAX);
+        assert_eq!(dice.min(), i32::MAX);
+
+        let dice: Dice = "1000d4294967295-5 advantage".parse().unwrap();
+        let roll = dice.roll(&mut random);
+
+        assert_eq!(dice.min(), 995);
+        assert_eq!(dice.max(), i32::MAX - 5);
+        assert_eq!(roll.rolls.len(), 1000);
+        assert_eq!(roll.total, i32::MAX - 5);
+    }
+
+    #[test]
+    fn test_too_many_dice() {
+        assert_eq!("1001d6".parse::<Dice>(), Err(ParseDiceError::InvalidCount));
+        assert_eq!(
+            Dice::new(u32::MAX, 6, 0, RollMode::Normal),
+            Dice::new(MAX_DICE, 6, 0, RollMode::Normal)
+        );
+        assert!("1000d6".parse::<Dice>().is_ok());
+    }
+
+    #[test]
+    fn test_single_sign() {
+        assert_eq!(
+            "1d6+-2".parse::<Dice>(),
+            Err(ParseDiceError::InvalidModifier)
+        );
+        assert_eq!(
+            "1d6-+2".parse::<Dice>(),
+            Err(ParseDiceError::InvalidModifier)
+        );
+        assert_eq!(
+            "1d6--2".parse::<Dice>(),
+            Err(ParseDiceError::InvalidModifier)
+        );
+        assert_eq!("1d6+".parse::<Dice>(), Err(ParseDiceError::InvalidModifier));
+        assert_eq!("1d6-2147483648".parse::<Dice>().unwrap().min(), -2147483647);
+    }
+
+    #[test]
+    fn test_display() {
+        for text in &["3d6+2", "1d20 advantage", "2d8-1 disadvantage", "1d4"] {
+            let dice: Dice = text.parse().unwrap();
+            assert_eq!(&dice.to_string(), text);
+        }
+    }
+}
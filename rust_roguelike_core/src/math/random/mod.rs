@@ -0,0 +1,274 @@
+pub mod dice;
+
+/// A small seedable pseudo random number generator.
+///
+/// It is based on [SplitMix64](https://prng.di.unimi.it/splitmix64.c)
+/// and returns the same numbers for the same seed on every platform.
+/// Its whole state is a single u64, which can be saved & restored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Creates a new generator from a seed
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    /// Restores a generator from a state returned by [`Random::get_state`]
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut random = Random::new(42);
+    /// random.next_u64();
+    /// let mut restored = Random::from_state(random.get_state());
+    ///
+    /// assert_eq!(restored.next_u64(), random.next_u64());
+    /// ```
+    pub fn from_state(state: u64) -> Random {
+        Random { state }
+    }
+
+    /// Returns the current state, which can be used to continue the sequence later
+    pub fn get_state(&self) -> u64 {
+        self.state
+    }
+
+    /// Returns the next random u64
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut a = Random::new(42);
+    /// let mut b = Random::new(42);
+    /// assert_eq!(a.next_u64(), b.next_u64());
+    /// ```
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns the next random u32
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Returns a random number between 0 (inclusive) and 1 (exclusive)
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut random = Random::new(7);
+    /// let value = random.next_f64();
+    /// assert!(value >= 0.0 && value < 1.0);
+    /// ```
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random number between `min` (inclusive) and `max` (exclusive)
+    ///
+    /// Returns `min` if the range is empty.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut random = Random::new(7);
+    /// let value = random.gen_range(3, 6);
+    /// assert!(value >= 3 && value < 6);
+    /// assert_eq!(random.gen_range(4, 4), 4);
+    /// ```
+    pub fn gen_range(&mut self, min: u32, max: u32) -> u32 {
+        if max <= min {
+            return min;
+        }
+
+        min + self.gen_below(max - min)
+    }
+
+    /// Returns a random number between `min` (inclusive) and `max` (exclusive)
+    ///
+    /// Returns `min` if the range is empty.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut random = Random::new(7);
+    /// let value = random.gen_range_i32(-3, 3);
+    /// assert!(value >= -3 && value < 3);
+    /// assert_eq!(random.gen_range_i32(-4, -4), -4);
+    /// ```
+    pub fn gen_range_i32(&mut self, min: i32, max: i32) -> i32 {
+        if max <= min {
+            return min;
+        }
+
+        let range = (max as i64 - min as i64) as u32;
+        (min as i64 + self.gen_below(range) as i64) as i32
+    }
+
+    /// Returns true with a probability of `percentage` percent
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut random = Random::new(7);
+    /// assert!(!random.check_percentage(0));
+    /// assert!(random.check_percentage(100));
+    /// ```
+    pub fn check_percentage(&mut self, percentage: u32) -> bool {
+        self.gen_below(100) < percentage
+    }
+
+    /// Returns a random element of the slice or `None` if it is empty
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut random = Random::new(7);
+    /// let empty: [u32; 0] = [];
+    /// assert!([1, 2, 3].contains(random.choose(&[1, 2, 3]).unwrap()));
+    /// assert_eq!(random.choose(&empty), None);
+    /// ```
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        items.get(self.gen_below(items.len() as u32) as usize)
+    }
+
+    /// Returns a random element of the slice, where each element is chosen proportional to its weight
+    ///
+    /// Returns `None` if the slice is empty or all weights are 0.
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut random = Random::new(7);
+    /// let items = [("orc", 0), ("goblin", 5), ("troll", 0)];
+    /// assert_eq!(random.choose_weighted(&items), Some(&"goblin"));
+    /// assert_eq!(random.choose_weighted(&items[..1]), None);
+    /// ```
+    pub fn choose_weighted<'a, T>(&mut self, items: &'a [(T, u32)]) -> Option<&'a T> {
+        let total: u64 = items.iter().map(|(_, weight)| u64::from(*weight)).sum();
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut value = self.gen_below_u64(total);
+
+        for (item, weight) in items {
+            let weight = u64::from(*weight);
+
+            if value < weight {
+                return Some(item);
+            }
+
+            value -= weight;
+        }
+
+        None
+    }
+
+    /// Shuffles the slice in place
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::random::Random;
+    /// let mut random = Random::new(7);
+    /// let mut items = [1, 2, 3, 4, 5];
+    /// random.shuffle(&mut items);
+    /// items.sort();
+    /// assert_eq!(items, [1, 2, 3, 4, 5]);
+    /// ```
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_below(i as u32 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// Returns an unbiased random number between 0 (inclusive) and `max` (exclusive)
+    ///
+    /// See [Lemire](https://arxiv.org/abs/1805.10941)
+    fn gen_below(&mut self, max: u32) -> u32 {
+        let threshold = max.wrapping_neg() % max;
+
+        loop {
+            let product = self.next_u32() as u64 * max as u64;
+
+            if product as u32 >= threshold {
+                return (product >> 32) as u32;
+            }
+        }
+    }
+
+    /// Returns an unbiased random number between 0 (inclusive) and `max` (exclusive)
+    fn gen_below_u64(&mut self, max: u64) -> u64 {
+        let threshold = max.wrapping_neg() % max;
+
+        loop {
+            let product = self.next_u64() as u128 * max as u128;
+
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_sequence_on_every_platform() {
+        let mut random = Random::new(1234567);
+
+        assert_eq!(random.next_u64(), 6457827717110365317);
+        assert_eq!(random.next_u64(), 3203168211198807973);
+        assert_eq!(random.next_u64(), 9817491932198370423);
+    }
+
+    #[test]
+    fn test_gen_range_covers_range() {
+        let mut random = Random::new(99);
+        let mut counts = [0; 6];
+
+        for _ in 0..6000 {
+            counts[random.gen_range(0, 6) as usize] += 1;
+        }
+
+        assert!(counts.iter().all(|count| *count > 800 && *count < 1200));
+    }
+
+    #[test]
+    fn test_gen_range_i32_full_range() {
+        let mut random = Random::new(5);
+
+        for _ in 0..100 {
+            let value = random.gen_range_i32(i32::MIN, i32::MAX);
+            assert!(value < i32::MAX);
+        }
+    }
+
+    #[test]
+    fn test_choose_weighted_with_huge_weights() {
+        let mut random = Random::new(3);
+        let items = [
+            ("common", u32::MAX),
+            ("rare", 1),
+            ("never", 0),
+            ("also", u32::MAX),
+        ];
+        let mut counts = [0; 3];
+
+        for _ in 0..1000 {
+            match random.choose_weighted(&items) {
+                Some(&"common") => counts[0] += 1,
+                Some(&"also") => counts[1] += 1,
+                item => panic!("Unexpected {:?}", item),
+            }
+        }
+
+        assert!(counts[0] > 400 && counts[1] > 400);
+    }
+}
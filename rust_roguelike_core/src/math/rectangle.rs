@@ -0,0 +1,85 @@
+use crate::math::size2d::Size2d;
+
+/// An axis-aligned rectangle of tiles
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rectangle {
+    x: u32,
+    y: u32,
+    size: Size2d,
+}
+
+impl Rectangle {
+    /// Creates a new Rectangle starting at the point (`x`, `y`)
+    pub fn new(x: u32, y: u32, size: Size2d) -> Rectangle {
+        Rectangle { x, y, size }
+    }
+
+    /// Returns the x-coordinate of the start point
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// Returns the y-coordinate of the start point
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    /// Returns the size of the rectangle
+    pub fn size(&self) -> Size2d {
+        self.size
+    }
+
+    /// Returns the x-coordinate after the last column of the rectangle
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::rectangle::Rectangle;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let rectangle = Rectangle::new(2, 3, Size2d::new(4, 5));
+    /// assert_eq!(rectangle.end_x(), 6);
+    /// ```
+    pub fn end_x(&self) -> u32 {
+        self.x + self.size.width()
+    }
+
+    /// Returns the y-coordinate after the last row of the rectangle
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::rectangle::Rectangle;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let rectangle = Rectangle::new(2, 3, Size2d::new(4, 5));
+    /// assert_eq!(rectangle.end_y(), 8);
+    /// ```
+    pub fn end_y(&self) -> u32 {
+        self.y + self.size.height()
+    }
+
+    /// Returns the point at the center of the rectangle
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::rectangle::Rectangle;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let rectangle = Rectangle::new(2, 3, Size2d::new(4, 5));
+    /// assert_eq!(rectangle.center(), [4, 5]);
+    /// ```
+    pub fn center(&self) -> [u32; 2] {
+        [
+            self.x + self.size.width() / 2,
+            self.y + self.size.height() / 2,
+        ]
+    }
+
+    /// Is the point inside the rectangle?
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::rectangle::Rectangle;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    /// let rectangle = Rectangle::new(2, 3, Size2d::new(4, 5));
+    /// assert!(rectangle.contains(2, 3));
+    /// assert!(rectangle.contains(5, 7));
+    /// assert!(!rectangle.contains(6, 7));
+    /// assert!(!rectangle.contains(5, 8));
+    /// ```
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && x < self.end_x() && y >= self.y && y < self.end_y()
+    }
+}
@@ -1,5 +1,6 @@
 use crate::interface::rendering::{Renderer, TextureId};
 use crate::math::color::Color;
+use crate::math::graph::hex::HexMap;
 
 const SIZE: [f32; 2] = [1.0, 1.0];
 
@@ -37,4 +38,38 @@ impl TileRenderer {
             .get_ascii_renderer(self.texture_id)
             .render_u8(point, SIZE, ascii, color);
     }
+
+    /// Renders the hexagon at `index` of the map with a uniform color
+    pub fn render_hex_full(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        map: &HexMap,
+        index: usize,
+        color: Color,
+    ) {
+        let center = map.get_center(index);
+        let corners = map.get_corners(index);
+        let color_renderer = renderer.get_color_renderer();
+
+        for i in 0..corners.len() {
+            let next = corners[(i + 1) % corners.len()];
+            color_renderer.render_triangle(center, corners[i], next, color);
+        }
+    }
+
+    /// Renders an ascii character in the center of the hexagon at `index` of the map
+    pub fn render_hex_ascii(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        map: &HexMap,
+        index: usize,
+        ascii: u8,
+        color: Color,
+    ) {
+        let [x, y] = map.get_center(index);
+        let point = [x - SIZE[0] / 2.0, y - SIZE[1] / 2.0];
+        renderer
+            .get_ascii_renderer(self.texture_id)
+            .render_u8(point, SIZE, ascii, color);
+    }
 }
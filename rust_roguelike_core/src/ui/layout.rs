@@ -0,0 +1,159 @@
+use crate::math::rectangle::Rectangle;
+use crate::math::size2d::Size2d;
+
+/// The length of a row or column of a layout
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Length {
+    /// A specific number of tiles
+    Fixed(u32),
+    /// Shares the remaining tiles with the other fills
+    Fill,
+}
+
+/// Splits an area into rows from top to bottom.
+///
+/// Fixed rows are shrunk from the last one, if the area is too small.
+///
+/// ```
+///# use rust_roguelike_core::math::rectangle::Rectangle;
+///# use rust_roguelike_core::math::size2d::Size2d;
+///# use rust_roguelike_core::ui::layout::{split_rows, Length};
+/// let area = Rectangle::new(0, 0, Size2d::new(80, 50));
+/// let rows = split_rows(&area, &[Length::Fill, Length::Fixed(10)]);
+///
+/// assert_eq!(rows, vec![
+///   Rectangle::new(0, 10, Size2d::new(80, 40)),
+///   Rectangle::new(0, 0, Size2d::new(80, 10)),
+/// ]);
+/// ```
+pub fn split_rows(area: &Rectangle, lengths: &[Length]) -> Vec<Rectangle> {
+    let mut top = area.end_y();
+
+    calculate_lengths(area.size().height(), lengths)
+        .into_iter()
+        .map(|height| {
+            top -= height;
+            Rectangle::new(area.x(), top, Size2d::new(area.size().width(), height))
+        })
+        .collect()
+}
+
+/// Splits an area into columns from left to right.
+///
+/// ```
+///# use rust_roguelike_core::math::rectangle::Rectangle;
+///# use rust_roguelike_core::math::size2d::Size2d;
+///# use rust_roguelike_core::ui::layout::{split_columns, Length};
+/// let area = Rectangle::new(0, 0, Size2d::new(80, 50));
+/// let columns = split_columns(&area, &[Length::Fixed(20), Length::Fill, Length::Fill]);
+///
+/// assert_eq!(columns, vec![
+///   Rectangle::new(0, 0, Size2d::new(20, 50)),
+///   Rectangle::new(20, 0, Size2d::new(30, 50)),
+///   Rectangle::new(50, 0, Size2d::new(30, 50)),
+/// ]);
+/// ```
+pub fn split_columns(area: &Rectangle, lengths: &[Length]) -> Vec<Rectangle> {
+    let mut left = area.x();
+
+    calculate_lengths(area.size().width(), lengths)
+        .into_iter()
+        .map(|width| {
+            let column = Rectangle::new(left, area.y(), Size2d::new(width, area.size().height()));
+            left += width;
+            column
+        })
+        .collect()
+}
+
+/// Returns the area without a margin on each side
+///
+/// ```
+///# use rust_roguelike_core::math::rectangle::Rectangle;
+///# use rust_roguelike_core::math::size2d::Size2d;
+///# use rust_roguelike_core::ui::layout::shrink;
+/// let area = Rectangle::new(2, 3, Size2d::new(10, 5));
+///
+/// assert_eq!(shrink(&area, 1), Rectangle::new(3, 4, Size2d::new(8, 3)));
+/// assert_eq!(shrink(&area, 3), Rectangle::new(5, 6, Size2d::new(4, 0)));
+/// ```
+pub fn shrink(area: &Rectangle, margin: u32) -> Rectangle {
+    let size = area.size();
+
+    Rectangle::new(
+        area.x() + margin,
+        area.y() + margin,
+        Size2d::new(
+            size.width().saturating_sub(2 * margin),
+            size.height().saturating_sub(2 * margin),
+        ),
+    )
+}
+
+fn calculate_lengths(total: u32, lengths: &[Length]) -> Vec<u32> {
+    let mut remaining = total;
+    let mut result: Vec<u32> = lengths
+        .iter()
+        .map(|length| match length {
+            Length::Fixed(fixed) => {
+                let fixed = (*fixed).min(remaining);
+                remaining -= fixed;
+                fixed
+            }
+            Length::Fill => 0,
+        })
+        .collect();
+    let fills = lengths.iter().filter(|l| **l == Length::Fill).count() as u32;
+
+    if let Some(share) = remaining.checked_div(fills) {
+        let mut extra = remaining % fills;
+
+        for (length, value) in lengths.iter().zip(result.iter_mut()) {
+            if *length == Length::Fill {
+                *value = share;
+
+                if extra > 0 {
+                    *value += 1;
+                    extra -= 1;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remainder_goes_to_first_fills() {
+        assert_eq!(
+            calculate_lengths(
+                11,
+                &[Length::Fill, Length::Fixed(2), Length::Fill, Length::Fill]
+            ),
+            vec![3, 2, 3, 3]
+        );
+    }
+
+    #[test]
+    fn test_too_small_area() {
+        assert_eq!(
+            calculate_lengths(5, &[Length::Fixed(3), Length::Fill, Length::Fixed(3)]),
+            vec![3, 0, 2]
+        );
+    }
+
+    #[test]
+    fn test_nested_layout() {
+        let area = Rectangle::new(0, 0, Size2d::new(10, 6));
+        let rows = split_rows(&area, &[Length::Fixed(1), Length::Fill]);
+        let columns = split_columns(&rows[1], &[Length::Fill, Length::Fixed(4)]);
+
+        assert_eq!(rows[0], Rectangle::new(0, 5, Size2d::new(10, 1)));
+        assert_eq!(columns[0], Rectangle::new(0, 0, Size2d::new(6, 5)));
+        assert_eq!(columns[1], Rectangle::new(6, 0, Size2d::new(4, 5)));
+    }
+}
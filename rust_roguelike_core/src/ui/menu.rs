@@ -0,0 +1,484 @@
+use crate::interface::input::{KeyCode, MouseButton};
+use crate::interface::rendering::{Renderer, TextureId};
+use crate::math::color::{Color, WHITE, YELLOW};
+use crate::math::rectangle::Rectangle;
+use crate::math::size2d::Size2d;
+use crate::ui::layout::shrink;
+use crate::ui::widget::Ui;
+
+/// The maximum number of items per page, one for each letter
+pub const MAX_PAGE_SIZE: usize = 26;
+
+/// The state of a [`Menu`] after handling input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MenuResult {
+    /// The menu is still waiting for a choice
+    Open,
+    /// The menu was closed without a choice
+    Cancelled,
+    /// The indices of the chosen items. A single-select menu returns exactly one.
+    Chosen(Vec<usize>),
+}
+
+/// A modal list like an inventory, where each item is chosen with a letter.
+///
+/// ```text
+/// ┌Inventory──────┐
+/// │a) Potion      │
+/// │b) Sword       │
+/// │< 1/2 >        │
+/// └───────────────┘
+/// ```
+///
+/// Items are chosen with their letter, a mouse click or the cursor moved by Up & Down and Enter.
+/// Left & Right, PageUp & PageDown or a click on `<` & `>` switch pages. Escape & the right mouse button cancel.
+///
+/// A multi-select menu toggles items instead and Enter confirms them.
+///
+/// ```
+///# use rust_roguelike_core::interface::input::KeyCode;
+///# use rust_roguelike_core::math::rectangle::Rectangle;
+///# use rust_roguelike_core::math::size2d::Size2d;
+///# use rust_roguelike_core::ui::menu::{Menu, MenuResult};
+/// let items = vec!["Potion".to_string(), "Sword".to_string(), "Shield".to_string()];
+/// let mut menu = Menu::new(0, Rectangle::new(0, 0, Size2d::new(20, 10)), "Drop", items, false);
+///
+/// assert_eq!(menu.on_key_released(KeyCode::Down), MenuResult::Open);
+/// assert_eq!(menu.on_key_released(KeyCode::Enter), MenuResult::Chosen(vec![1]));
+/// assert_eq!(menu.on_key_released(KeyCode::C), MenuResult::Chosen(vec![2]));
+/// assert_eq!(menu.on_key_released(KeyCode::D), MenuResult::Open);
+/// assert_eq!(menu.on_key_released(KeyCode::Escape), MenuResult::Cancelled);
+/// ```
+pub struct Menu {
+    ui: Ui,
+    area: Rectangle,
+    title: String,
+    items: Vec<String>,
+    is_multi_select: bool,
+    is_selected: Vec<bool>,
+    cursor: usize,
+    size: Option<Size2d>,
+    pub color: Color,
+    pub highlight: Color,
+}
+
+impl Menu {
+    /// Creates a new Menu with the cursor at the first item
+    pub fn new(
+        texture_id: TextureId,
+        area: Rectangle,
+        title: &str,
+        items: Vec<String>,
+        is_multi_select: bool,
+    ) -> Self {
+        let is_selected = vec![false; items.len()];
+
+        Menu {
+            ui: Ui::new(texture_id),
+            area,
+            title: title.to_string(),
+            items,
+            is_multi_select,
+            is_selected,
+            cursor: 0,
+            size: None,
+            color: WHITE,
+            highlight: YELLOW,
+        }
+    }
+
+    /// Returns the index of the item under the cursor
+    pub fn get_cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the indices of the items selected in a multi-select menu
+    pub fn get_selected(&self) -> Vec<usize> {
+        self.is_selected
+            .iter()
+            .enumerate()
+            .filter(|(_, is_selected)| **is_selected)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the number of items per page
+    ///
+    /// ```
+    ///# use rust_roguelike_core::math::rectangle::Rectangle;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::ui::menu::Menu;
+    /// let items: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+    /// let menu = Menu::new(0, Rectangle::new(0, 0, Size2d::new(20, 6)), "Items", items, false);
+    ///
+    /// // 4 rows inside the frame minus 1 row for the page number
+    /// assert_eq!(menu.get_page_size(), 3);
+    /// assert_eq!(menu.get_page_count(), 4);
+    /// ```
+    pub fn get_page_size(&self) -> usize {
+        let rows = self.get_rows();
+
+        if self.items.len() <= rows {
+            rows.max(1)
+        } else {
+            rows.saturating_sub(1).max(1)
+        }
+    }
+
+    /// Returns the number of pages, which is at least 1
+    pub fn get_page_count(&self) -> usize {
+        let page_size = self.get_page_size();
+        self.items.len().div_ceil(page_size).max(1)
+    }
+
+    /// Returns the page of the cursor
+    pub fn get_page(&self) -> usize {
+        self.cursor / self.get_page_size()
+    }
+
+    /// Handles keyboard input
+    pub fn on_key_released(&mut self, key: KeyCode) -> MenuResult {
+        if self.items.is_empty() {
+            return match key {
+                KeyCode::Escape | KeyCode::Enter => MenuResult::Cancelled,
+                _ => MenuResult::Open,
+            };
+        }
+
+        let page_size = self.get_page_size();
+        let last = self.items.len() - 1;
+
+        match key {
+            KeyCode::Escape => return MenuResult::Cancelled,
+            KeyCode::Up => self.cursor = self.cursor.checked_sub(1).unwrap_or(last),
+            KeyCode::Down => {
+                self.cursor = if self.cursor < last {
+                    self.cursor + 1
+                } else {
+                    0
+                }
+            }
+            KeyCode::Left | KeyCode::PageUp => self.show_previous_page(),
+            KeyCode::Right | KeyCode::PageDown => self.show_next_page(),
+            KeyCode::Space if self.is_multi_select => return self.choose(self.cursor),
+            KeyCode::Enter => {
+                if self.is_multi_select {
+                    return self.confirm();
+                }
+
+                return self.choose(self.cursor);
+            }
+            _ => {
+                if let Some(letter) = get_letter(key) {
+                    let index = self.get_page() * page_size + letter;
+
+                    if letter < page_size && index <= last {
+                        self.cursor = index;
+                        return self.choose(index);
+                    }
+                }
+            }
+        }
+
+        MenuResult::Open
+    }
+
+    /// Handles mouse input. Needs the size of the render target, so the menu must have been rendered before.
+    pub fn on_button_released(&mut self, button: MouseButton, index: usize) -> MenuResult {
+        if button == MouseButton::Right {
+            return MenuResult::Cancelled;
+        }
+
+        let size = match self.size {
+            Some(size) if button == MouseButton::Left => size,
+            _ => return MenuResult::Open,
+        };
+
+        let [x, y] = size.to_point(index);
+        let inner = self.get_inner_area();
+
+        if !inner.contains(x, y) {
+            return MenuResult::Open;
+        }
+
+        let row = (inner.end_y() - 1 - y) as usize;
+        let page_size = self.get_page_size();
+        let index = self.get_page() * page_size + row;
+
+        if row == page_size && self.has_page_indicator() {
+            let column = (x - inner.x()) as usize;
+
+            if column == 0 {
+                self.show_previous_page();
+            } else if column + 1 == self.get_page_text().len() {
+                self.show_next_page();
+            }
+        } else if row < page_size && index < self.items.len() {
+            self.cursor = index;
+            return self.choose(index);
+        }
+
+        MenuResult::Open
+    }
+
+    /// Renders the current page
+    pub fn render(&mut self, renderer: &mut dyn Renderer) {
+        self.size = Some(renderer.get_size());
+        let inner = self.ui.panel(renderer, &self.area, &self.title, self.color);
+        let page_size = self.get_page_size();
+        let page = self.get_page();
+        let start = page * page_size;
+        let end = (start + page_size).min(self.items.len());
+
+        for (row, index) in (start..end).enumerate().take(self.get_rows()) {
+            let area = get_row(&inner, row);
+            let letter = (b'a' + row as u8) as char;
+            let text = if self.is_multi_select {
+                let mark = if self.is_selected[index] { 'x' } else { ' ' };
+                format!("{}) [{}] {}", letter, mark, self.items[index])
+            } else {
+                format!("{}) {}", letter, self.items[index])
+            };
+            let color = if index == self.cursor {
+                self.highlight
+            } else {
+                self.color
+            };
+
+            self.ui.label(renderer, &area, &text, color);
+        }
+
+        if self.has_page_indicator() {
+            let area = get_row(&inner, page_size);
+            let text = self.get_page_text();
+            self.ui.label(renderer, &area, &text, self.color);
+        }
+    }
+
+    fn get_inner_area(&self) -> Rectangle {
+        shrink(&self.area, 1)
+    }
+
+    /// Returns the number of rows inside the frame, which can be used
+    fn get_rows(&self) -> usize {
+        (self.get_inner_area().size().height() as usize).min(MAX_PAGE_SIZE)
+    }
+
+    /// Is there a row left for the page indicator?
+    fn has_page_indicator(&self) -> bool {
+        self.get_page_count() > 1 && self.get_page_size() < self.get_rows()
+    }
+
+    fn get_page_text(&self) -> String {
+        format!("< {}/{} >", self.get_page() + 1, self.get_page_count())
+    }
+
+    fn show_previous_page(&mut self) {
+        let page_size = self.get_page_size();
+        self.cursor = self.cursor.saturating_sub(page_size) / page_size * page_size;
+    }
+
+    fn show_next_page(&mut self) {
+        let next_page = (self.get_page() + 1) * self.get_page_size();
+
+        if next_page < self.items.len() {
+            self.cursor = next_page;
+        }
+    }
+
+    fn choose(&mut self, index: usize) -> MenuResult {
+        if self.is_multi_select {
+            self.is_selected[index] = !self.is_selected[index];
+            MenuResult::Open
+        } else {
+            MenuResult::Chosen(vec![index])
+        }
+    }
+
+    fn confirm(&self) -> MenuResult {
+        let selected = self.get_selected();
+
+        if selected.is_empty() {
+            MenuResult::Chosen(vec![self.cursor])
+        } else {
+            MenuResult::Chosen(selected)
+        }
+    }
+}
+
+fn get_row(area: &Rectangle, row: usize) -> Rectangle {
+    Rectangle::new(
+        area.x(),
+        area.end_y().saturating_sub(1 + row as u32),
+        Size2d::new(area.size().width(), 1),
+    )
+}
+
+fn get_letter(key: KeyCode) -> Option<usize> {
+    let key = key as u32;
+
+    if key >= KeyCode::A as u32 && key <= KeyCode::Z as u32 {
+        Some((key - KeyCode::A as u32) as usize)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::mock::MockRenderer;
+
+    fn create(count: usize, height: u32, is_multi_select: bool) -> Menu {
+        let items = (0..count).map(|i| format!("Item {}", i)).collect();
+        let area = Rectangle::new(0, 0, Size2d::new(20, height));
+        Menu::new(0, area, "Inventory", items, is_multi_select)
+    }
+
+    #[test]
+    fn test_paging() {
+        let mut menu = create(7, 5, false);
+
+        assert_eq!(menu.get_page_size(), 2);
+        assert_eq!(menu.get_page_count(), 4);
+
+        menu.on_key_released(KeyCode::PageDown);
+        assert_eq!(menu.get_cursor(), 2);
+        assert_eq!(
+            menu.on_key_released(KeyCode::B),
+            MenuResult::Chosen(vec![3])
+        );
+
+        menu.on_key_released(KeyCode::Right);
+        menu.on_key_released(KeyCode::Right);
+        menu.on_key_released(KeyCode::Right);
+        assert_eq!(menu.get_cursor(), 6);
+        assert_eq!(menu.on_key_released(KeyCode::B), MenuResult::Open);
+
+        menu.on_key_released(KeyCode::Left);
+        assert_eq!(menu.get_cursor(), 4);
+        menu.on_key_released(KeyCode::Down);
+        menu.on_key_released(KeyCode::Down);
+        assert_eq!(menu.get_page(), 3);
+    }
+
+    #[test]
+    fn test_cursor_wraps_around() {
+        let mut menu = create(3, 10, false);
+
+        menu.on_key_released(KeyCode::Up);
+        assert_eq!(menu.get_cursor(), 2);
+        menu.on_key_released(KeyCode::Down);
+        assert_eq!(menu.get_cursor(), 0);
+    }
+
+    #[test]
+    fn test_multi_select() {
+        let mut menu = create(4, 10, true);
+
+        assert_eq!(menu.on_key_released(KeyCode::A), MenuResult::Open);
+        assert_eq!(menu.on_key_released(KeyCode::C), MenuResult::Open);
+        assert_eq!(menu.on_key_released(KeyCode::D), MenuResult::Open);
+        assert_eq!(menu.on_key_released(KeyCode::Space), MenuResult::Open);
+        assert_eq!(menu.get_selected(), vec![0, 2]);
+        assert_eq!(
+            menu.on_key_released(KeyCode::Enter),
+            MenuResult::Chosen(vec![0, 2])
+        );
+    }
+
+    #[test]
+    fn test_empty_menu() {
+        let mut menu = create(0, 10, false);
+
+        assert_eq!(menu.get_page_count(), 1);
+        assert_eq!(menu.on_key_released(KeyCode::A), MenuResult::Open);
+        assert_eq!(menu.on_key_released(KeyCode::Down), MenuResult::Open);
+        assert_eq!(menu.on_key_released(KeyCode::Enter), MenuResult::Cancelled);
+    }
+
+    #[test]
+    fn test_render_page() {
+        let mut menu = create(5, 5, true);
+        let mut renderer = MockRenderer::new(Size2d::new(20, 10));
+        menu.on_key_released(KeyCode::PageDown);
+        menu.on_key_released(KeyCode::A);
+        menu.render(&mut renderer);
+
+        assert_eq!(
+            renderer.get_lines(1, 1, 18, 3),
+            vec!["a) [x] Item 2", "b) [ ] Item 3", "< 2/3 >"]
+        );
+        assert_eq!(renderer.get_text(1.0, 3.0).unwrap(), "a) [x] Item 2");
+        assert_eq!(renderer.texts[renderer.texts.len() - 3].2, YELLOW);
+    }
+
+    #[test]
+    fn test_no_space_for_page_indicator() {
+        let mut renderer = MockRenderer::new(Size2d::new(20, 10));
+
+        for height in 2..4 {
+            let mut menu = create(5, height, false);
+            menu.render(&mut renderer);
+
+            assert_eq!(menu.get_page_size(), 1);
+            assert!(!renderer
+                .texts
+                .iter()
+                .any(|(_, text, _)| text.starts_with('<')));
+        }
+
+        let mut menu = create(5, 2, false);
+        renderer.texts.clear();
+        menu.render(&mut renderer);
+
+        assert!(!renderer
+            .texts
+            .iter()
+            .any(|(_, text, _)| text.starts_with("a)")));
+    }
+
+    #[test]
+    fn test_mouse_changes_pages() {
+        let mut menu = create(5, 5, false);
+        let size = Size2d::new(20, 10);
+        menu.render(&mut MockRenderer::new(size));
+
+        // The page indicator "< 1/3 >" is in the bottom row of the inner area
+        menu.on_button_released(MouseButton::Left, size.to_index(7, 1));
+        assert_eq!(menu.get_page(), 1);
+        menu.on_button_released(MouseButton::Left, size.to_index(7, 1));
+        menu.on_button_released(MouseButton::Left, size.to_index(7, 1));
+        assert_eq!(menu.get_page(), 2);
+        menu.on_button_released(MouseButton::Left, size.to_index(3, 1));
+        assert_eq!(menu.get_page(), 2);
+        menu.on_button_released(MouseButton::Left, size.to_index(1, 1));
+        assert_eq!(menu.get_page(), 1);
+    }
+
+    #[test]
+    fn test_mouse() {
+        let mut menu = create(5, 5, false);
+        let size = Size2d::new(20, 10);
+
+        assert_eq!(
+            menu.on_button_released(MouseButton::Left, size.to_index(3, 2)),
+            MenuResult::Open
+        );
+
+        menu.render(&mut MockRenderer::new(size));
+
+        assert_eq!(
+            menu.on_button_released(MouseButton::Left, size.to_index(3, 2)),
+            MenuResult::Chosen(vec![1])
+        );
+        assert_eq!(
+            menu.on_button_released(MouseButton::Left, size.to_index(3, 1)),
+            MenuResult::Open
+        );
+        assert_eq!(
+            menu.on_button_released(MouseButton::Right, 0),
+            MenuResult::Cancelled
+        );
+    }
+}
@@ -0,0 +1,433 @@
+use crate::interface::input::KeyCode;
+use crate::interface::rendering::{Renderer, TextureId};
+use crate::math::color::{Color, WHITE};
+use crate::math::rectangle::Rectangle;
+use std::collections::VecDeque;
+
+const SIZE: [f32; 2] = [1.0, 1.0];
+
+/// A part of a message with its own color
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub color: Color,
+}
+
+impl Span {
+    /// Creates a new Span
+    pub fn new(text: &str, color: Color) -> Self {
+        Span {
+            text: text.to_string(),
+            color,
+        }
+    }
+}
+
+/// A message like "You hit the orc."
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub spans: Vec<Span>,
+    /// The turn of the last repetition
+    pub turn: u32,
+    /// How often the message was repeated in a row
+    pub count: u32,
+}
+
+impl Message {
+    /// Returns the text without colors
+    pub fn get_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+/// The history of the latest messages.
+///
+/// A message repeated in a row is only stored once with its count.
+///
+/// ```
+///# use rust_roguelike_core::math::color::{RED, WHITE};
+///# use rust_roguelike_core::ui::message::{MessageLog, Span};
+/// let mut log = MessageLog::new(2);
+/// log.add(1, "You wait.", WHITE);
+/// log.add(2, "You hit the orc.", WHITE);
+/// log.add(3, "You hit the orc.", WHITE);
+/// log.add_spans(4, vec![Span::new("The orc ", WHITE), Span::new("dies", RED), Span::new(".", WHITE)]);
+///
+/// assert_eq!(log.len(), 2);
+/// assert_eq!(log.get_messages()[0].get_text(), "You hit the orc.");
+/// assert_eq!(log.get_messages()[0].count, 2);
+/// assert_eq!(log.get_messages()[0].turn, 3);
+/// assert_eq!(log.get_messages()[1].get_text(), "The orc dies.");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MessageLog {
+    messages: VecDeque<Message>,
+    capacity: usize,
+}
+
+impl MessageLog {
+    /// Creates a new MessageLog, which keeps at most `capacity` messages
+    pub fn new(capacity: usize) -> Self {
+        MessageLog {
+            messages: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Adds a message with a single color
+    pub fn add(&mut self, turn: u32, text: &str, color: Color) {
+        self.add_spans(turn, vec![Span::new(text, color)]);
+    }
+
+    /// Adds a message with multiple colors. The oldest message is removed, if the log is full.
+    pub fn add_spans(&mut self, turn: u32, spans: Vec<Span>) {
+        if let Some(last) = self.messages.back_mut() {
+            if last.spans == spans {
+                last.count += 1;
+                last.turn = turn;
+                return;
+            }
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+
+        self.messages.push_back(Message {
+            spans,
+            turn,
+            count: 1,
+        });
+    }
+
+    /// Returns the messages from oldest to newest
+    pub fn get_messages(&self) -> &VecDeque<Message> {
+        &self.messages
+    }
+
+    /// Returns the number of messages
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+}
+
+/// Splits colored text into lines of at most `width` characters.
+///
+/// Lines are broken between words & words longer than a line are split.
+///
+/// ```
+///# use rust_roguelike_core::math::color::{RED, WHITE};
+///# use rust_roguelike_core::ui::message::{wrap_spans, Span};
+/// let spans = vec![Span::new("You hit the ", WHITE), Span::new("orc", RED), Span::new(".", WHITE)];
+/// let lines = wrap_spans(&spans, 11);
+///
+/// assert_eq!(lines, vec![
+///   vec![Span::new("You hit the", WHITE)],
+///   vec![Span::new("orc", RED), Span::new(".", WHITE)],
+/// ]);
+/// ```
+pub fn wrap_spans(spans: &[Span], width: usize) -> Vec<Vec<Span>> {
+    let chars: Vec<(char, Color)> = spans
+        .iter()
+        .flat_map(|span| span.text.chars().map(move |c| (c, span.color)))
+        .collect();
+    let mut lines = Vec::new();
+    let mut line: Vec<(char, Color)> = Vec::new();
+    let mut i = 0;
+
+    if width == 0 {
+        return lines;
+    }
+
+    while i < chars.len() {
+        let start = i;
+        let is_space = chars[i].0.is_whitespace();
+
+        while i < chars.len() && chars[i].0.is_whitespace() == is_space {
+            i += 1;
+        }
+
+        if is_space {
+            continue;
+        }
+
+        let spaces = chars[..start]
+            .iter()
+            .rev()
+            .take_while(|(c, _)| c.is_whitespace())
+            .count();
+        let word = &chars[start..i];
+
+        if !line.is_empty() {
+            if line.len() + spaces + word.len() <= width {
+                line.extend_from_slice(&chars[start - spaces..start]);
+            } else {
+                lines.push(to_spans(&line));
+                line.clear();
+            }
+        }
+
+        for c in word {
+            if line.len() == width {
+                lines.push(to_spans(&line));
+                line.clear();
+            }
+
+            line.push(*c);
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(to_spans(&line));
+    }
+
+    lines
+}
+
+fn to_spans(chars: &[(char, Color)]) -> Vec<Span> {
+    let mut spans: Vec<Span> = Vec::new();
+
+    for (c, color) in chars {
+        match spans.last_mut() {
+            Some(span) if span.color == *color => span.text.push(*c),
+            _ => spans.push(Span {
+                text: c.to_string(),
+                color: *color,
+            }),
+        }
+    }
+
+    spans
+}
+
+/// Renders the newest messages of a [`MessageLog`] into a rectangular area.
+///
+/// PageUp & PageDown scroll through older messages.
+pub struct MessageLogView {
+    texture_id: TextureId,
+    area: Rectangle,
+    show_turns: bool,
+    scroll: usize,
+}
+
+impl MessageLogView {
+    /// Creates a new MessageLogView, which optionally starts each message with its turn
+    pub fn new(texture_id: TextureId, area: Rectangle, show_turns: bool) -> Self {
+        MessageLogView {
+            texture_id,
+            area,
+            show_turns,
+            scroll: 0,
+        }
+    }
+
+    /// Returns the number of lines the view is scrolled up from the newest message
+    pub fn get_scroll(&self) -> usize {
+        self.scroll
+    }
+
+    /// Returns all lines of the log from oldest to newest
+    pub fn get_lines(&self, log: &MessageLog) -> Vec<Vec<Span>> {
+        let width = self.area.size().width() as usize;
+
+        log.get_messages()
+            .iter()
+            .flat_map(|message| {
+                let mut spans = message.spans.clone();
+                let color = spans.last().map(|span| span.color).unwrap_or(WHITE);
+
+                if self.show_turns {
+                    spans.insert(0, Span::new(&format!("[{}] ", message.turn), color));
+                }
+
+                if message.count > 1 {
+                    spans.push(Span::new(&format!(" x{}", message.count), color));
+                }
+
+                wrap_spans(&spans, width)
+            })
+            .collect()
+    }
+
+    /// Returns the lines inside the area from top to bottom
+    ///
+    /// ```
+    ///# use rust_roguelike_core::interface::input::KeyCode;
+    ///# use rust_roguelike_core::math::color::WHITE;
+    ///# use rust_roguelike_core::math::rectangle::Rectangle;
+    ///# use rust_roguelike_core::math::size2d::Size2d;
+    ///# use rust_roguelike_core::ui::message::{MessageLog, MessageLogView};
+    /// let mut log = MessageLog::new(10);
+    /// log.add(1, "A", WHITE);
+    /// log.add(2, "B", WHITE);
+    /// log.add(3, "B", WHITE);
+    /// log.add(4, "C", WHITE);
+    /// let mut view = MessageLogView::new(0, Rectangle::new(0, 0, Size2d::new(10, 2)), false);
+    /// let get_texts = |view: &MessageLogView| -> Vec<String> {
+    ///     view.get_visible_lines(&log).iter().map(|line| line[0].text.clone()).collect()
+    /// };
+    ///
+    /// assert_eq!(get_texts(&view), vec!["B x2", "C"]);
+    ///
+    /// view.on_key_released(KeyCode::PageUp, &log);
+    ///
+    /// assert_eq!(get_texts(&view), vec!["A", "B x2"]);
+    /// ```
+    pub fn get_visible_lines(&self, log: &MessageLog) -> Vec<Vec<Span>> {
+        let lines = self.get_lines(log);
+        let height = self.area.size().height() as usize;
+        let end = lines.len() - self.scroll.min(lines.len());
+        let start = end.saturating_sub(height);
+
+        lines[start..end].to_vec()
+    }
+
+    /// Scrolls one page up or down. Returns true, if the key was handled.
+    pub fn on_key_released(&mut self, key: KeyCode, log: &MessageLog) -> bool {
+        let page = (self.area.size().height() as usize)
+            .saturating_sub(1)
+            .max(1);
+        let max_scroll = self
+            .get_lines(log)
+            .len()
+            .saturating_sub(self.area.size().height() as usize);
+
+        match key {
+            KeyCode::PageUp => self.scroll = (self.scroll + page).min(max_scroll),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_sub(page),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Scrolls back to the newest message
+    pub fn scroll_to_newest(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Renders the visible lines. The newest message is at the bottom.
+    pub fn render(&self, renderer: &mut dyn Renderer, log: &MessageLog) {
+        let ascii_renderer = renderer.get_ascii_renderer(self.texture_id);
+        let top = self.area.end_y() as f32 - 1.0;
+
+        for (row, line) in self.get_visible_lines(log).iter().enumerate() {
+            let y = top - row as f32;
+            let mut x = self.area.x() as f32;
+
+            for span in line {
+                ascii_renderer.render_text([x, y], SIZE, &span.text, span.color);
+                x += span.text.chars().count() as f32;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::mock::MockRenderer;
+    use crate::math::color::{RED, YELLOW};
+    use crate::math::size2d::Size2d;
+
+    #[test]
+    fn test_collapse_only_in_a_row() {
+        let mut log = MessageLog::new(10);
+        log.add(1, "You hit the orc.", WHITE);
+        log.add(2, "You hit the orc.", RED);
+        log.add(3, "You hit the orc.", RED);
+        log.add(4, "You hit the orc.", RED);
+        log.add(5, "You hit the orc.", WHITE);
+
+        let counts: Vec<u32> = log.get_messages().iter().map(|m| m.count).collect();
+
+        assert_eq!(counts, vec![1, 3, 1]);
+    }
+
+    #[test]
+    fn test_bounded_history() {
+        let mut log = MessageLog::new(3);
+
+        for turn in 0..5 {
+            log.add(turn, &turn.to_string(), WHITE);
+        }
+
+        let texts: Vec<String> = log.get_messages().iter().map(|m| m.get_text()).collect();
+
+        assert_eq!(texts, vec!["2", "3", "4"]);
+
+        log.clear();
+
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn test_wrap_long_words() {
+        let lines = wrap_spans(&[Span::new("ab  abcdefg c", WHITE)], 3);
+        let texts: Vec<&str> = lines.iter().map(|line| line[0].text.as_str()).collect();
+
+        assert_eq!(texts, vec!["ab", "abc", "def", "g c"]);
+        assert!(wrap_spans(&[Span::new("   ", WHITE)], 3).is_empty());
+        assert!(wrap_spans(&[Span::new("abc", WHITE)], 0).is_empty());
+    }
+
+    #[test]
+    fn test_scrolling_is_clamped() {
+        let mut log = MessageLog::new(10);
+
+        for turn in 0..5 {
+            log.add(turn, &turn.to_string(), WHITE);
+        }
+
+        let mut view = MessageLogView::new(0, Rectangle::new(0, 0, Size2d::new(5, 3)), false);
+
+        assert!(view.on_key_released(KeyCode::PageUp, &log));
+        assert_eq!(view.get_scroll(), 2);
+        assert!(view.on_key_released(KeyCode::PageUp, &log));
+        assert_eq!(view.get_scroll(), 2);
+        assert!(view.on_key_released(KeyCode::PageDown, &log));
+        assert_eq!(view.get_scroll(), 0);
+        assert!(!view.on_key_released(KeyCode::Enter, &log));
+    }
+
+    #[test]
+    fn test_render() {
+        let mut log = MessageLog::new(10);
+        log.add(7, "You wait.", WHITE);
+        log.add_spans(
+            8,
+            vec![
+                Span::new("The orc is ", WHITE),
+                Span::new("burning", YELLOW),
+                Span::new(".", WHITE),
+            ],
+        );
+        log.add(9, "You hit it.", RED);
+        log.add(10, "You hit it.", RED);
+        let view = MessageLogView::new(0, Rectangle::new(2, 1, Size2d::new(12, 3)), true);
+        let mut renderer = MockRenderer::new(Size2d::new(20, 10));
+
+        view.render(&mut renderer, &log);
+
+        assert_eq!(
+            renderer.get_lines(2, 1, 12, 3),
+            vec!["is burning.", "[10] You hit", "it. x2"]
+        );
+        assert_eq!(renderer.get_text(5.0, 3.0), Some("burning"));
+        assert_eq!(renderer.texts[1].2, YELLOW);
+        assert_eq!(renderer.get_text(2.0, 2.0), Some("[10] You hit"));
+        assert_eq!(renderer.texts.len(), 5);
+    }
+}
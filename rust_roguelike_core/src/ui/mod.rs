@@ -0,0 +1,4 @@
+pub mod layout;
+pub mod menu;
+pub mod message;
+pub mod widget;
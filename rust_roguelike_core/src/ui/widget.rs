@@ -0,0 +1,401 @@
+use crate::interface::input::MouseButton;
+use crate::interface::rendering::{Renderer, TextureId};
+use crate::math::color::Color;
+use crate::math::rectangle::Rectangle;
+use crate::math::size2d::Size2d;
+use crate::ui::layout::shrink;
+
+const SIZE: [f32; 2] = [1.0, 1.0];
+
+/// The box-drawing glyphs of code page 437
+pub const HORIZONTAL: u8 = 196;
+pub const VERTICAL: u8 = 179;
+pub const TOP_LEFT: u8 = 218;
+pub const TOP_RIGHT: u8 = 191;
+pub const BOTTOM_LEFT: u8 = 192;
+pub const BOTTOM_RIGHT: u8 = 217;
+
+/// Renders widgets on the tile grid & reports their interactions immediately.
+///
+/// The widgets are rendered every frame & a button returns true in the frame after it was clicked:
+///
+/// ```
+///# use rust_roguelike_core::interface::App;
+///# use rust_roguelike_core::interface::input::MouseButton;
+///# use rust_roguelike_core::interface::rendering::Renderer;
+///# use rust_roguelike_core::math::color::WHITE;
+///# use rust_roguelike_core::math::rectangle::Rectangle;
+///# use rust_roguelike_core::ui::layout::{split_rows, Length};
+///# use rust_roguelike_core::ui::widget::Ui;
+/// struct Example {
+///     ui: Ui,
+///     is_sound_on: bool,
+///     clicks: u32,
+/// }
+///
+/// impl App for Example {
+///     fn render(&mut self, renderer: &mut dyn Renderer) {
+///         let area = Rectangle::new(0, 0, renderer.get_size());
+///         let inner = self.ui.panel(renderer, &area, "Options", WHITE);
+///         let rows = split_rows(&inner, &[Length::Fixed(1), Length::Fixed(1)]);
+///
+///         if self.ui.button(renderer, &rows[0], "Click", WHITE) {
+///             self.clicks += 1;
+///         }
+///
+///         self.ui.checkbox(renderer, &rows[1], "Sound", &mut self.is_sound_on, WHITE);
+///         self.ui.finish();
+///     }
+///
+///     fn on_button_released(&mut self, button: MouseButton, index: usize) {
+///         self.ui.on_button_released(button, index);
+///     }
+/// }
+/// ```
+pub struct Ui {
+    texture_id: TextureId,
+    click: Option<usize>,
+}
+
+impl Ui {
+    /// Creates a new Ui
+    pub fn new(texture_id: TextureId) -> Self {
+        Ui {
+            texture_id,
+            click: None,
+        }
+    }
+
+    /// Remembers a left click for the widgets of the next frame
+    pub fn on_button_released(&mut self, button: MouseButton, index: usize) {
+        if button == MouseButton::Left {
+            self.click = Some(index);
+        }
+    }
+
+    /// Finishes the frame & forgets clicks no widget used
+    pub fn finish(&mut self) {
+        self.click = None;
+    }
+
+    /// Renders a frame with a title & returns the area inside it
+    pub fn panel(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        area: &Rectangle,
+        title: &str,
+        color: Color,
+    ) -> Rectangle {
+        let size = area.size();
+
+        if size.width() < 2 || size.height() < 2 {
+            return shrink(area, 1);
+        }
+
+        let left = area.x() as f32;
+        let right = (area.end_x() - 1) as f32;
+        let bottom = area.y() as f32;
+        let top = (area.end_y() - 1) as f32;
+        let ascii_renderer = renderer.get_ascii_renderer(self.texture_id);
+
+        for x in (area.x() + 1)..(area.end_x() - 1) {
+            ascii_renderer.render_u8([x as f32, top], SIZE, HORIZONTAL, color);
+            ascii_renderer.render_u8([x as f32, bottom], SIZE, HORIZONTAL, color);
+        }
+
+        for y in (area.y() + 1)..(area.end_y() - 1) {
+            ascii_renderer.render_u8([left, y as f32], SIZE, VERTICAL, color);
+            ascii_renderer.render_u8([right, y as f32], SIZE, VERTICAL, color);
+        }
+
+        ascii_renderer.render_u8([left, top], SIZE, TOP_LEFT, color);
+        ascii_renderer.render_u8([right, top], SIZE, TOP_RIGHT, color);
+        ascii_renderer.render_u8([left, bottom], SIZE, BOTTOM_LEFT, color);
+        ascii_renderer.render_u8([right, bottom], SIZE, BOTTOM_RIGHT, color);
+
+        let title = truncate(title, size.width() - 2);
+
+        if !title.is_empty() {
+            ascii_renderer.render_text([left + 1.0, top], SIZE, &title, color);
+        }
+
+        shrink(area, 1)
+    }
+
+    /// Renders text in the top row of the area. It is cut off at the area's width.
+    pub fn label(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        area: &Rectangle,
+        text: &str,
+        color: Color,
+    ) {
+        let text = truncate(text, area.size().width());
+
+        if text.is_empty() || area.size().height() == 0 {
+            return;
+        }
+
+        renderer.get_ascii_renderer(self.texture_id).render_text(
+            [area.x() as f32, (area.end_y() - 1) as f32],
+            SIZE,
+            &text,
+            color,
+        );
+    }
+
+    /// Renders a button & returns true, if it was clicked
+    pub fn button(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        area: &Rectangle,
+        text: &str,
+        color: Color,
+    ) -> bool {
+        self.label(renderer, area, text, color);
+        self.take_click(renderer, area).is_some()
+    }
+
+    /// Renders a checkbox like `[x] Sound` & toggles it, if it was clicked.
+    /// Returns true, if it changed.
+    pub fn checkbox(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        area: &Rectangle,
+        text: &str,
+        is_checked: &mut bool,
+        color: Color,
+    ) -> bool {
+        let is_clicked = self.take_click(renderer, area).is_some();
+
+        if is_clicked {
+            *is_checked = !*is_checked;
+        }
+
+        let mark = if *is_checked { 'x' } else { ' ' };
+        self.label(renderer, area, &format!("[{}] {}", mark, text), color);
+
+        is_clicked
+    }
+
+    /// Renders a bar filled according to `value` & `max`, like a health bar
+    pub fn progress_bar(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        area: &Rectangle,
+        value: u32,
+        max: u32,
+        color: Color,
+        background: Color,
+    ) {
+        let width = area.size().width() as f32;
+        let height = area.size().height() as f32;
+        let ratio = if max == 0 {
+            0.0
+        } else {
+            value.min(max) as f32 / max as f32
+        };
+        let filled = width * ratio;
+        let position = [area.x() as f32, area.y() as f32];
+        let color_renderer = renderer.get_color_renderer();
+
+        if filled > 0.0 {
+            color_renderer.render_rectangle(position, [filled, height], color);
+        }
+
+        if filled < width {
+            color_renderer.render_rectangle(
+                [position[0] + filled, position[1]],
+                [width - filled, height],
+                background,
+            );
+        }
+    }
+
+    /// Renders a list with an item per row from top to bottom & selects an item, if it was clicked.
+    /// Returns true, if the selection changed.
+    ///
+    /// Items, which don't fit into the area, are skipped.
+    pub fn list(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        area: &Rectangle,
+        items: &[&str],
+        selected: &mut Option<usize>,
+        color: Color,
+        selected_color: Color,
+    ) -> bool {
+        let mut is_changed = false;
+
+        if let Some([_, y]) = self.take_click(renderer, area) {
+            let row = (area.end_y() - 1 - y) as usize;
+
+            if row < items.len() && *selected != Some(row) {
+                *selected = Some(row);
+                is_changed = true;
+            }
+        }
+
+        let rows = (area.size().height() as usize).min(items.len());
+
+        for (row, item) in items.iter().take(rows).enumerate() {
+            let item_area = Rectangle::new(
+                area.x(),
+                area.end_y() - 1 - row as u32,
+                Size2d::new(area.size().width(), 1),
+            );
+            let item_color = if *selected == Some(row) {
+                selected_color
+            } else {
+                color
+            };
+
+            self.label(renderer, &item_area, item, item_color);
+        }
+
+        is_changed
+    }
+
+    /// Returns & forgets the clicked point, if it is inside the area
+    fn take_click(&mut self, renderer: &dyn Renderer, area: &Rectangle) -> Option<[u32; 2]> {
+        let point = renderer.get_size().to_point(self.click?);
+
+        if area.contains(point[0], point[1]) {
+            self.click = None;
+            Some(point)
+        } else {
+            None
+        }
+    }
+}
+
+fn truncate(text: &str, width: u32) -> String {
+    text.chars().take(width as usize).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::mock::MockRenderer;
+    use crate::math::color::{BLACK, GREEN, RED, WHITE, YELLOW};
+
+    fn create() -> (Ui, MockRenderer) {
+        (Ui::new(0), MockRenderer::new(Size2d::new(20, 10)))
+    }
+
+    #[test]
+    fn test_panel() {
+        let (mut ui, mut renderer) = create();
+        let inner = ui.panel(
+            &mut renderer,
+            &Rectangle::new(1, 2, Size2d::new(6, 4)),
+            "Inventory",
+            WHITE,
+        );
+
+        let glyph = |x, y| {
+            renderer
+                .get_text(x, y)
+                .map(|s| s.chars().next().unwrap() as u8)
+        };
+
+        assert_eq!(inner, Rectangle::new(2, 3, Size2d::new(4, 2)));
+        assert_eq!(glyph(1.0, 5.0), Some(TOP_LEFT));
+        assert_eq!(glyph(6.0, 5.0), Some(TOP_RIGHT));
+        assert_eq!(glyph(1.0, 2.0), Some(BOTTOM_LEFT));
+        assert_eq!(glyph(6.0, 2.0), Some(BOTTOM_RIGHT));
+        assert_eq!(glyph(1.0, 3.0), Some(VERTICAL));
+        assert_eq!(glyph(3.0, 2.0), Some(HORIZONTAL));
+        assert_eq!(renderer.texts.last().unwrap().1, "Inve");
+    }
+
+    #[test]
+    fn test_label_is_cut_off() {
+        let (mut ui, mut renderer) = create();
+        ui.label(
+            &mut renderer,
+            &Rectangle::new(2, 1, Size2d::new(5, 2)),
+            "Hello World",
+            WHITE,
+        );
+
+        assert_eq!(renderer.get_text(2.0, 2.0), Some("Hello"));
+    }
+
+    #[test]
+    fn test_button_uses_click_once() {
+        let (mut ui, mut renderer) = create();
+        let area = Rectangle::new(2, 1, Size2d::new(5, 1));
+        ui.on_button_released(MouseButton::Left, 24);
+
+        assert!(ui.button(&mut renderer, &area, "Ok", WHITE));
+        assert!(!ui.button(&mut renderer, &area, "Ok", WHITE));
+
+        ui.on_button_released(MouseButton::Right, 24);
+
+        assert!(!ui.button(&mut renderer, &area, "Ok", WHITE));
+
+        ui.on_button_released(MouseButton::Left, 30);
+
+        assert!(!ui.button(&mut renderer, &area, "Ok", WHITE));
+
+        ui.on_button_released(MouseButton::Left, 24);
+
+        ui.finish();
+
+        assert!(!ui.button(&mut renderer, &area, "Ok", WHITE));
+    }
+
+    #[test]
+    fn test_checkbox() {
+        let (mut ui, mut renderer) = create();
+        let area = Rectangle::new(0, 0, Size2d::new(10, 1));
+        let mut is_checked = false;
+        ui.on_button_released(MouseButton::Left, 3);
+
+        assert!(ui.checkbox(&mut renderer, &area, "Sound", &mut is_checked, WHITE));
+        assert!(is_checked);
+        assert_eq!(renderer.get_text(0.0, 0.0), Some("[x] Sound"));
+        assert!(!ui.checkbox(&mut renderer, &area, "Sound", &mut is_checked, WHITE));
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        let (mut ui, mut renderer) = create();
+        let area = Rectangle::new(1, 2, Size2d::new(10, 1));
+        ui.progress_bar(&mut renderer, &area, 3, 4, GREEN, RED);
+        ui.progress_bar(&mut renderer, &area, 5, 4, GREEN, RED);
+        ui.progress_bar(&mut renderer, &area, 0, 0, GREEN, RED);
+
+        assert_eq!(
+            renderer.rectangles,
+            vec![
+                ([1.0, 2.0], [7.5, 1.0], GREEN),
+                ([8.5, 2.0], [2.5, 1.0], RED),
+                ([1.0, 2.0], [10.0, 1.0], GREEN),
+                ([1.0, 2.0], [10.0, 1.0], RED),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_selection() {
+        let (mut ui, mut renderer) = create();
+        let area = Rectangle::new(0, 5, Size2d::new(10, 3));
+        let items = ["Potion", "Sword", "Shield", "Ring"];
+        let mut selected = None;
+        ui.on_button_released(MouseButton::Left, Size2d::new(20, 10).to_index(4, 6));
+
+        assert!(ui.list(&mut renderer, &area, &items, &mut selected, WHITE, YELLOW));
+        assert_eq!(selected, Some(1));
+        assert_eq!(
+            renderer.get_lines(0, 5, 10, 3),
+            vec!["Potion", "Sword", "Shield"]
+        );
+        assert_eq!(renderer.texts[1].2, YELLOW);
+
+        ui.on_button_released(MouseButton::Left, Size2d::new(20, 10).to_index(4, 6));
+
+        assert!(!ui.list(&mut renderer, &area, &items, &mut selected, WHITE, BLACK));
+    }
+}